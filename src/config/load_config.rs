@@ -0,0 +1,384 @@
+use std::collections::HashSet;
+
+use anyhow::anyhow;
+
+use crate::{
+    config::structs::{Config, ConfigFile, EntryMode, MarketPair, PairDefaults, PairLeg, TpSlMode},
+    discovery::discover_pairs::contract_ratio,
+};
+
+/// Reads the config file at `path` and checks it before the bot starts trading.
+pub fn load_config(path: &str) -> anyhow::Result<Config> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("Failed to read config file {}: {}", path, e))?;
+    let config_file = toml::from_str::<ConfigFile>(&contents)
+        .map_err(|e| anyhow!("Failed to parse config file {}: {}", path, e))?;
+
+    let config = resolve_config(config_file);
+    validate_config(&config)?;
+
+    Ok(config)
+}
+
+/// Builds a pair that uses the default settings for everything.
+pub fn pair_from_defaults(
+    extended_market: &str,
+    pacifica_market: &str,
+    defaults: &PairDefaults,
+) -> MarketPair {
+    MarketPair {
+        extended: PairLeg {
+            market: extended_market.to_string(),
+            slippage: defaults.extended_slippage,
+        },
+        pacifica: PairLeg {
+            market: pacifica_market.to_string(),
+            slippage: defaults.pacifica_slippage,
+        },
+        buy_amount: defaults.buy_amount,
+        funding_rate_threshold: defaults.funding_rate_threshold,
+        price_spread_threshold: defaults.price_spread_threshold,
+        orphan_policy: defaults.orphan_policy,
+        entry_mode: defaults.entry_mode,
+        maker_timeout_secs: defaults.maker_timeout_secs,
+        twap_slices: defaults.twap_slices,
+        twap_window_secs: defaults.twap_window_secs,
+        tp_sl: defaults.tp_sl,
+        expected_holding_hours: defaults.expected_holding_hours,
+        estimated_slippage: defaults.estimated_slippage,
+        min_net_edge: defaults.min_net_edge,
+        exit: defaults.exit,
+        sizing: defaults.sizing,
+    }
+}
+
+fn resolve_config(config_file: ConfigFile) -> Config {
+    let defaults = config_file.defaults;
+    let pairs = config_file
+        .pairs
+        .into_iter()
+        .map(|pair| MarketPair {
+            extended: PairLeg {
+                market: pair.extended,
+                slippage: pair.extended_slippage.unwrap_or(defaults.extended_slippage),
+            },
+            pacifica: PairLeg {
+                market: pair.pacifica,
+                slippage: pair.pacifica_slippage.unwrap_or(defaults.pacifica_slippage),
+            },
+            buy_amount: pair.buy_amount.unwrap_or(defaults.buy_amount),
+            funding_rate_threshold: pair
+                .funding_rate_threshold
+                .unwrap_or(defaults.funding_rate_threshold),
+            price_spread_threshold: pair
+                .price_spread_threshold
+                .unwrap_or(defaults.price_spread_threshold),
+            orphan_policy: pair.orphan_policy.unwrap_or(defaults.orphan_policy),
+            entry_mode: pair.entry_mode.unwrap_or(defaults.entry_mode),
+            maker_timeout_secs: pair
+                .maker_timeout_secs
+                .unwrap_or(defaults.maker_timeout_secs),
+            twap_slices: pair.twap_slices.unwrap_or(defaults.twap_slices),
+            twap_window_secs: pair.twap_window_secs.unwrap_or(defaults.twap_window_secs),
+            tp_sl: pair
+                .tp_sl
+                .map(|tp_sl| defaults.tp_sl.with_override(&tp_sl))
+                .unwrap_or(defaults.tp_sl),
+            expected_holding_hours: pair
+                .expected_holding_hours
+                .unwrap_or(defaults.expected_holding_hours),
+            estimated_slippage: pair
+                .estimated_slippage
+                .unwrap_or(defaults.estimated_slippage),
+            min_net_edge: pair.min_net_edge.unwrap_or(defaults.min_net_edge),
+            exit: pair
+                .exit
+                .map(|exit| defaults.exit.with_override(&exit))
+                .unwrap_or(defaults.exit),
+            sizing: pair
+                .sizing
+                .map(|sizing| defaults.sizing.with_override(&sizing))
+                .unwrap_or(defaults.sizing),
+        })
+        .collect();
+
+    Config {
+        target_minute: config_file.target_minute,
+        extended: config_file.extended,
+        pacifica: config_file.pacifica,
+        defaults,
+        discovery: config_file.discovery,
+        fees: config_file.fees,
+        dry_run: config_file.dry_run,
+        rebalance: config_file.rebalance,
+        allocation: config_file.allocation,
+        pairs,
+    }
+}
+
+fn validate_config(config: &Config) -> anyhow::Result<()> {
+    if config.target_minute >= 60 {
+        return Err(anyhow!(
+            "target_minute must be between 0 and 59, got {}",
+            config.target_minute
+        ));
+    }
+
+    for http in [&config.extended, &config.pacifica] {
+        if !http.base_url.starts_with("http://") && !http.base_url.starts_with("https://") {
+            return Err(anyhow!("Invalid base_url: {}", http.base_url));
+        }
+        if http.request_timeout_secs == 0 || http.connect_timeout_secs == 0 {
+            return Err(anyhow!(
+                "Timeouts for {} must be at least one second",
+                http.base_url
+            ));
+        }
+    }
+
+    for (field, value) in [
+        ("pacifica_maker_fee", config.fees.pacifica_maker_fee),
+        ("pacifica_taker_fee", config.fees.pacifica_taker_fee),
+    ] {
+        if !value.is_finite() || !(0.0..1.0).contains(&value) {
+            return Err(anyhow!(
+                "Fee {} must be between 0 and 1, got {}",
+                field,
+                value
+            ));
+        }
+    }
+
+    let dry_run = &config.dry_run;
+    if !(dry_run.extended_balance >= 0.0 && dry_run.pacifica_balance >= 0.0) {
+        return Err(anyhow!("Dry run balances must not be negative"));
+    }
+    if !dry_run.leverage.is_finite() || dry_run.leverage < 1.0 {
+        return Err(anyhow!("Dry run leverage must be at least 1"));
+    }
+    for (field, value) in [
+        ("extended_maker_fee", dry_run.extended_maker_fee),
+        ("extended_taker_fee", dry_run.extended_taker_fee),
+        ("pacifica_maker_fee", dry_run.pacifica_maker_fee),
+        ("pacifica_taker_fee", dry_run.pacifica_taker_fee),
+    ] {
+        if !value.is_finite() || !(0.0..1.0).contains(&value) {
+            return Err(anyhow!(
+                "Dry run {} must be between 0 and 1, got {}",
+                field,
+                value
+            ));
+        }
+    }
+
+    let tolerance = config.rebalance.tolerance;
+    if !tolerance.is_finite() || !(0.0..1.0).contains(&tolerance) {
+        return Err(anyhow!(
+            "Rebalance tolerance must be between 0 and 1, got {}",
+            tolerance
+        ));
+    }
+
+    let allocation = &config.allocation;
+    if allocation.max_new_pairs == 0 {
+        return Err(anyhow!("max_new_pairs must be at least 1"));
+    }
+    for (field, value) in [
+        ("max_market_exposure", allocation.max_market_exposure),
+        ("max_total_exposure", allocation.max_total_exposure),
+    ] {
+        if !value.is_finite() || value < 0.0 {
+            return Err(anyhow!("{} must not be negative, got {}", field, value));
+        }
+    }
+    let utilization = allocation.max_margin_utilization;
+    if !utilization.is_finite() || utilization <= 0.0 || utilization > 1.0 {
+        return Err(anyhow!(
+            "max_margin_utilization must be above 0 and at most 1, got {}",
+            utilization
+        ));
+    }
+
+    if config.pairs.is_empty() && !config.discovery.enabled {
+        return Err(anyhow!(
+            "Config must contain at least one pair or enable discovery"
+        ));
+    }
+
+    // Discovered pairs are traded with the defaults, so they must be valid on their own
+    validate_pair(&pair_from_defaults(
+        "defaults",
+        "defaults",
+        &config.defaults,
+    ))?;
+
+    let mut extended_markets = HashSet::new();
+    let mut pacifica_markets = HashSet::new();
+
+    for pair in config.pairs.iter() {
+        if !extended_markets.insert(pair.extended.market.as_str()) {
+            return Err(anyhow!(
+                "Extended market {} is configured more than once",
+                pair.extended.market
+            ));
+        }
+        if !pacifica_markets.insert(pair.pacifica.market.as_str()) {
+            return Err(anyhow!(
+                "Pacifica market {} is configured more than once",
+                pair.pacifica.market
+            ));
+        }
+        // Entries open the same quantity on both venues, so the contracts must match
+        let ratio = contract_ratio(&pair.extended.market, &pair.pacifica.market);
+        if ratio != 1.0 {
+            return Err(anyhow!(
+                "Pair {}/{} trades contracts of different sizes, one {} contract is {} {} contracts",
+                pair.extended.market,
+                pair.pacifica.market,
+                pair.pacifica.market,
+                ratio,
+                pair.extended.market
+            ));
+        }
+        validate_pair(pair)?;
+    }
+
+    Ok(())
+}
+
+fn validate_pair(pair: &MarketPair) -> anyhow::Result<()> {
+    let name = format!("{}/{}", pair.extended.market, pair.pacifica.market);
+
+    if pair.extended.market.is_empty() || pair.pacifica.market.is_empty() {
+        return Err(anyhow!("Pair {} has an empty market name", name));
+    }
+    if !pair.buy_amount.is_finite() || pair.buy_amount <= 0.0 {
+        return Err(anyhow!("Pair {} must have a positive buy_amount", name));
+    }
+    if !pair.funding_rate_threshold.is_finite() || pair.funding_rate_threshold < 0.0 {
+        return Err(anyhow!(
+            "Pair {} must have a non-negative funding_rate_threshold",
+            name
+        ));
+    }
+    if !pair.price_spread_threshold.is_finite() || pair.price_spread_threshold < 0.0 {
+        return Err(anyhow!(
+            "Pair {} must have a non-negative price_spread_threshold",
+            name
+        ));
+    }
+    if pair.entry_mode == EntryMode::Maker && pair.maker_timeout_secs == 0 {
+        return Err(anyhow!(
+            "Pair {} must have a positive maker_timeout_secs",
+            name
+        ));
+    }
+    if pair.entry_mode == EntryMode::Twap && pair.twap_slices == 0 {
+        return Err(anyhow!("Pair {} must have at least one twap slice", name));
+    }
+    if !pair.expected_holding_hours.is_finite() || pair.expected_holding_hours <= 0.0 {
+        return Err(anyhow!(
+            "Pair {} must have a positive expected_holding_hours",
+            name
+        ));
+    }
+    if !pair.estimated_slippage.is_finite() || !(0.0..1.0).contains(&pair.estimated_slippage) {
+        return Err(anyhow!(
+            "Pair {} estimated_slippage must be between 0 and 1, got {}",
+            name,
+            pair.estimated_slippage
+        ));
+    }
+    if !pair.min_net_edge.is_finite() {
+        return Err(anyhow!("Pair {} must have a finite min_net_edge", name));
+    }
+    let exit = &pair.exit;
+    if !exit.exit_threshold.is_finite() || exit.exit_threshold > pair.funding_rate_threshold {
+        return Err(anyhow!(
+            "Pair {} exit_threshold must not be above the funding_rate_threshold, got {}",
+            name,
+            exit.exit_threshold
+        ));
+    }
+    for (field, value) in [
+        ("basis_take_profit", exit.basis_take_profit),
+        ("max_holding_hours", exit.max_holding_hours),
+    ] {
+        if !value.is_finite() || value < 0.0 {
+            return Err(anyhow!("Pair {} must have a non-negative {}", name, field));
+        }
+    }
+    let sizing = &pair.sizing;
+    if !sizing.leverage.is_finite() || sizing.leverage < 1.0 {
+        return Err(anyhow!("Pair {} leverage must be at least 1", name));
+    }
+    if !sizing.collateral_usage.is_finite()
+        || sizing.collateral_usage <= 0.0
+        || sizing.collateral_usage > 1.0
+    {
+        return Err(anyhow!(
+            "Pair {} collateral_usage must be above 0 and at most 1, got {}",
+            name,
+            sizing.collateral_usage
+        ));
+    }
+    if !sizing.equity_fraction.is_finite() || !(0.0..=1.0).contains(&sizing.equity_fraction) {
+        return Err(anyhow!(
+            "Pair {} equity_fraction must be between 0 and 1, got {}",
+            name,
+            sizing.equity_fraction
+        ));
+    }
+    if !sizing.full_size_edge.is_finite() || sizing.full_size_edge < 0.0 {
+        return Err(anyhow!(
+            "Pair {} must have a non-negative full_size_edge",
+            name
+        ));
+    }
+    let tp_sl = &pair.tp_sl;
+    if tp_sl.enabled {
+        for (field, value) in [
+            ("take_profit", tp_sl.take_profit),
+            ("stop_loss", tp_sl.stop_loss),
+        ] {
+            if !value.is_finite() || value <= 0.0 {
+                return Err(anyhow!("Pair {} must have a positive {}", name, field));
+            }
+            if tp_sl.mode == TpSlMode::Percent && value >= 1.0 {
+                return Err(anyhow!(
+                    "Pair {} {} must be below 1 in percent mode, got {}",
+                    name,
+                    field,
+                    value
+                ));
+            }
+        }
+        if !tp_sl.limit_offset.is_finite() || !(0.0..1.0).contains(&tp_sl.limit_offset) {
+            return Err(anyhow!(
+                "Pair {} limit_offset must be between 0 and 1, got {}",
+                name,
+                tp_sl.limit_offset
+            ));
+        }
+        if tp_sl.mode == TpSlMode::Percent && tp_sl.stop_loss + tp_sl.limit_offset >= 1.0 {
+            return Err(anyhow!(
+                "Pair {} stop loss limit price would not be positive",
+                name
+            ));
+        }
+        if tp_sl.mode == TpSlMode::Atr && tp_sl.atr_period == 0 {
+            return Err(anyhow!("Pair {} must have a positive atr_period", name));
+        }
+    }
+    for leg in [&pair.extended, &pair.pacifica] {
+        if leg.slippage.is_nan() || leg.slippage <= 0.0 || leg.slippage >= 1.0 {
+            return Err(anyhow!(
+                "Slippage for {} must be between 0 and 1, got {}",
+                leg.market,
+                leg.slippage
+            ));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,2 @@
+pub mod load_config;
+pub mod structs;
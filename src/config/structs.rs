@@ -0,0 +1,591 @@
+use serde::Deserialize;
+
+/// Layout of the config file as written on disk.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigFile {
+    pub target_minute: u32,
+    #[serde(default = "default_extended_http")]
+    pub extended: VenueHttpConfig,
+    #[serde(default = "default_pacifica_http")]
+    pub pacifica: VenueHttpConfig,
+    pub defaults: PairDefaults,
+    #[serde(default)]
+    pub discovery: DiscoveryConfig,
+    #[serde(default)]
+    pub fees: FeeConfig,
+    #[serde(default)]
+    pub dry_run: DryRunConfig,
+    #[serde(default)]
+    pub rebalance: RebalanceConfig,
+    #[serde(default)]
+    pub allocation: AllocationConfig,
+    #[serde(default)]
+    pub pairs: Vec<PairEntry>,
+}
+
+/// Where and how to reach a venue's REST API.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct VenueHttpConfig {
+    pub base_url: String,
+    #[serde(default = "default_request_timeout_secs")]
+    pub request_timeout_secs: u64,
+    #[serde(default = "default_connect_timeout_secs")]
+    pub connect_timeout_secs: u64,
+}
+
+fn default_extended_http() -> VenueHttpConfig {
+    VenueHttpConfig {
+        base_url: String::from("https://api.starknet.extended.exchange"),
+        request_timeout_secs: default_request_timeout_secs(),
+        connect_timeout_secs: default_connect_timeout_secs(),
+    }
+}
+
+fn default_pacifica_http() -> VenueHttpConfig {
+    VenueHttpConfig {
+        base_url: String::from("https://api.pacifica.fi"),
+        request_timeout_secs: default_request_timeout_secs(),
+        connect_timeout_secs: default_connect_timeout_secs(),
+    }
+}
+
+fn default_request_timeout_secs() -> u64 {
+    10
+}
+
+fn default_connect_timeout_secs() -> u64 {
+    5
+}
+
+/// Values used by every pair that does not override them.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct PairDefaults {
+    pub buy_amount: f64,
+    pub funding_rate_threshold: f64,
+    pub price_spread_threshold: f64,
+    pub extended_slippage: f64,
+    pub pacifica_slippage: f64,
+    #[serde(default)]
+    pub orphan_policy: OrphanPolicy,
+    #[serde(default)]
+    pub entry_mode: EntryMode,
+    #[serde(default = "default_maker_timeout_secs")]
+    pub maker_timeout_secs: u64,
+    #[serde(default = "default_twap_slices")]
+    pub twap_slices: u32,
+    #[serde(default = "default_twap_window_secs")]
+    pub twap_window_secs: u64,
+    #[serde(default)]
+    pub tp_sl: TpSlPolicy,
+    #[serde(default = "default_expected_holding_hours")]
+    pub expected_holding_hours: f64,
+    #[serde(default = "default_estimated_slippage")]
+    pub estimated_slippage: f64,
+    #[serde(default)]
+    pub min_net_edge: f64,
+    #[serde(default)]
+    pub exit: ExitPolicy,
+    #[serde(default)]
+    pub sizing: SizingPolicy,
+}
+
+fn default_expected_holding_hours() -> f64 {
+    72.0
+}
+
+fn default_estimated_slippage() -> f64 {
+    0.0001
+}
+
+/// How the legs of a new pair are opened.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryMode {
+    /// Market orders on both venues.
+    #[default]
+    Taker,
+    /// Post-only limit order on Extended, each fill hedged with a market order on Pacifica.
+    Maker,
+    /// Several smaller hedged child pairs sent over a time window.
+    Twap,
+}
+
+fn default_maker_timeout_secs() -> u64 {
+    60
+}
+
+fn default_twap_slices() -> u32 {
+    4
+}
+
+fn default_twap_window_secs() -> u64 {
+    120
+}
+
+/// Take profit and stop loss attached to every leg the bot opens.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TpSlPolicy {
+    #[serde(default = "default_tp_sl_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub mode: TpSlMode,
+    /// Distance of the take profit trigger from the entry price, as a fraction
+    /// of the price or as a multiple of the ATR depending on `mode`.
+    #[serde(default = "default_take_profit")]
+    pub take_profit: f64,
+    /// Distance of the stop loss trigger from the entry price, in the same unit.
+    #[serde(default = "default_stop_loss")]
+    pub stop_loss: f64,
+    /// Distance of the limit price past the trigger price, always as a fraction
+    /// of the entry price.
+    #[serde(default = "default_tp_sl_limit_offset")]
+    pub limit_offset: f64,
+    /// Number of hourly candles the ATR is averaged over.
+    #[serde(default = "default_atr_period")]
+    pub atr_period: usize,
+}
+
+impl Default for TpSlPolicy {
+    fn default() -> Self {
+        TpSlPolicy {
+            enabled: default_tp_sl_enabled(),
+            mode: TpSlMode::default(),
+            take_profit: default_take_profit(),
+            stop_loss: default_stop_loss(),
+            limit_offset: default_tp_sl_limit_offset(),
+            atr_period: default_atr_period(),
+        }
+    }
+}
+
+impl TpSlPolicy {
+    /// The policy with the values a pair overrides replaced.
+    pub fn with_override(&self, tp_sl: &TpSlOverride) -> TpSlPolicy {
+        TpSlPolicy {
+            enabled: tp_sl.enabled.unwrap_or(self.enabled),
+            mode: tp_sl.mode.unwrap_or(self.mode),
+            take_profit: tp_sl.take_profit.unwrap_or(self.take_profit),
+            stop_loss: tp_sl.stop_loss.unwrap_or(self.stop_loss),
+            limit_offset: tp_sl.limit_offset.unwrap_or(self.limit_offset),
+            atr_period: tp_sl.atr_period.unwrap_or(self.atr_period),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TpSlMode {
+    /// Distances are fractions of the entry price.
+    #[default]
+    Percent,
+    /// Distances are multiples of the average true range of hourly candles.
+    Atr,
+}
+
+/// Per pair changes to the default `TpSlPolicy`.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct TpSlOverride {
+    pub enabled: Option<bool>,
+    pub mode: Option<TpSlMode>,
+    pub take_profit: Option<f64>,
+    pub stop_loss: Option<f64>,
+    pub limit_offset: Option<f64>,
+    pub atr_period: Option<usize>,
+}
+
+fn default_tp_sl_enabled() -> bool {
+    true
+}
+
+fn default_take_profit() -> f64 {
+    0.05
+}
+
+fn default_stop_loss() -> f64 {
+    0.05
+}
+
+fn default_tp_sl_limit_offset() -> f64 {
+    0.005
+}
+
+fn default_atr_period() -> usize {
+    14
+}
+
+/// When an open pair is closed, see `strategy::close_if_necessary`.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ExitPolicy {
+    /// Funding rate difference, in percent per hour and signed so that positive
+    /// favors the open pair, below which the pair is closed. Must be below the
+    /// entry threshold so small moves around it do not close and reopen the pair.
+    #[serde(default)]
+    pub exit_threshold: f64,
+    /// Funding periods the pair is held before the funding rates may close it.
+    #[serde(default)]
+    pub min_funding_periods: u32,
+    /// Price profit of both legs together, in percent of the notional, at which
+    /// the pair is closed. Zero turns it off.
+    #[serde(default)]
+    pub basis_take_profit: f64,
+    /// Hours after which the pair is closed whatever the rates. Zero turns it off.
+    #[serde(default)]
+    pub max_holding_hours: f64,
+}
+
+impl ExitPolicy {
+    /// The policy with the values a pair overrides replaced.
+    pub fn with_override(&self, exit: &ExitOverride) -> ExitPolicy {
+        ExitPolicy {
+            exit_threshold: exit.exit_threshold.unwrap_or(self.exit_threshold),
+            min_funding_periods: exit.min_funding_periods.unwrap_or(self.min_funding_periods),
+            basis_take_profit: exit.basis_take_profit.unwrap_or(self.basis_take_profit),
+            max_holding_hours: exit.max_holding_hours.unwrap_or(self.max_holding_hours),
+        }
+    }
+}
+
+/// Per pair changes to the default `ExitPolicy`.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ExitOverride {
+    pub exit_threshold: Option<f64>,
+    pub min_funding_periods: Option<u32>,
+    pub basis_take_profit: Option<f64>,
+    pub max_holding_hours: Option<f64>,
+}
+
+/// How large a new pair is, see `sizing::size_position`.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SizingPolicy {
+    /// Leverage the accounts trade at, used to work out the margin a pair locks.
+    #[serde(default = "default_leverage")]
+    pub leverage: f64,
+    /// Fraction of the available collateral on each venue a new pair may lock.
+    #[serde(default = "default_collateral_usage")]
+    pub collateral_usage: f64,
+    /// Margin per leg as a fraction of the smaller equity of the two venues, so
+    /// the size grows with profits. Zero opens the fixed `buy_amount` instead.
+    #[serde(default)]
+    pub equity_fraction: f64,
+    /// Net expected edge, in percent of the notional, at which the full size is
+    /// opened. Smaller edges open proportionally less, zero turns this off.
+    #[serde(default)]
+    pub full_size_edge: f64,
+}
+
+impl Default for SizingPolicy {
+    fn default() -> Self {
+        SizingPolicy {
+            leverage: default_leverage(),
+            collateral_usage: default_collateral_usage(),
+            equity_fraction: 0.0,
+            full_size_edge: 0.0,
+        }
+    }
+}
+
+impl SizingPolicy {
+    /// The policy with the values a pair overrides replaced.
+    pub fn with_override(&self, sizing: &SizingOverride) -> SizingPolicy {
+        SizingPolicy {
+            leverage: sizing.leverage.unwrap_or(self.leverage),
+            collateral_usage: sizing.collateral_usage.unwrap_or(self.collateral_usage),
+            equity_fraction: sizing.equity_fraction.unwrap_or(self.equity_fraction),
+            full_size_edge: sizing.full_size_edge.unwrap_or(self.full_size_edge),
+        }
+    }
+}
+
+/// Per pair changes to the default `SizingPolicy`.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct SizingOverride {
+    pub leverage: Option<f64>,
+    pub collateral_usage: Option<f64>,
+    pub equity_fraction: Option<f64>,
+    pub full_size_edge: Option<f64>,
+}
+
+fn default_leverage() -> f64 {
+    1.0
+}
+
+fn default_collateral_usage() -> f64 {
+    0.9
+}
+
+/// What to do with a position whose counterpart on the other venue is missing.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OrphanPolicy {
+    /// Close the orphaned leg.
+    Close,
+    /// Open the missing leg again on the other venue.
+    Rehedge,
+    /// Leave the position open and raise an alert.
+    #[default]
+    Alert,
+}
+
+/// Matching of markets listed on both venues, see `discovery::discover_pairs`.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct DiscoveryConfig {
+    /// Look for markets listed on both venues at the start of every cycle.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Trade discovered pairs with the default settings instead of only
+    /// logging them as proposals.
+    #[serde(default)]
+    pub auto_enable: bool,
+    /// Extended markets that discovery must never propose.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+/// Fee rates of the Pacifica account, as fractions of the notional. Pacifica
+/// does not serve them over the API, Extended's are fetched per market.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct FeeConfig {
+    #[serde(default = "default_pacifica_maker_fee")]
+    pub pacifica_maker_fee: f64,
+    #[serde(default = "default_pacifica_taker_fee")]
+    pub pacifica_taker_fee: f64,
+}
+
+impl Default for FeeConfig {
+    fn default() -> Self {
+        FeeConfig {
+            pacifica_maker_fee: default_pacifica_maker_fee(),
+            pacifica_taker_fee: default_pacifica_taker_fee(),
+        }
+    }
+}
+
+/// Paper trading with live market data, see `venue::paper_venue::PaperVenue`.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct DryRunConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Simulated starting balance in USD on each venue.
+    #[serde(default = "default_paper_balance")]
+    pub extended_balance: f64,
+    #[serde(default = "default_paper_balance")]
+    pub pacifica_balance: f64,
+    /// Leverage used to work out the margin locked by simulated positions.
+    #[serde(default = "default_paper_leverage")]
+    pub leverage: f64,
+    /// Fee rates used to price entries, as fractions of the notional. The
+    /// venues only serve the account's rates to authenticated requests.
+    #[serde(default)]
+    pub extended_maker_fee: f64,
+    #[serde(default = "default_extended_taker_fee")]
+    pub extended_taker_fee: f64,
+    #[serde(default = "default_pacifica_maker_fee")]
+    pub pacifica_maker_fee: f64,
+    #[serde(default = "default_pacifica_taker_fee")]
+    pub pacifica_taker_fee: f64,
+}
+
+impl Default for DryRunConfig {
+    fn default() -> Self {
+        DryRunConfig {
+            enabled: false,
+            extended_balance: default_paper_balance(),
+            pacifica_balance: default_paper_balance(),
+            leverage: default_paper_leverage(),
+            extended_maker_fee: 0.0,
+            extended_taker_fee: default_extended_taker_fee(),
+            pacifica_maker_fee: default_pacifica_maker_fee(),
+            pacifica_taker_fee: default_pacifica_taker_fee(),
+        }
+    }
+}
+
+fn default_paper_balance() -> f64 {
+    1000.0
+}
+
+fn default_paper_leverage() -> f64 {
+    1.0
+}
+
+fn default_extended_taker_fee() -> f64 {
+    0.00025
+}
+
+fn default_pacifica_maker_fee() -> f64 {
+    0.00015
+}
+
+fn default_pacifica_taker_fee() -> f64 {
+    0.0004
+}
+
+/// Repair of hedges whose legs drifted apart, see `strategy::repair_imbalance`.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct RebalanceConfig {
+    #[serde(default = "default_rebalance_enabled")]
+    pub enabled: bool,
+    /// Allowed size difference between the legs, as a fraction of the larger leg.
+    #[serde(default = "default_rebalance_tolerance")]
+    pub tolerance: f64,
+    #[serde(default)]
+    pub repair: ImbalanceRepair,
+}
+
+impl Default for RebalanceConfig {
+    fn default() -> Self {
+        RebalanceConfig {
+            enabled: default_rebalance_enabled(),
+            tolerance: default_rebalance_tolerance(),
+            repair: ImbalanceRepair::default(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImbalanceRepair {
+    /// Reduce-only order on the larger leg.
+    #[default]
+    Reduce,
+    /// Opening order on the smaller leg.
+    Hedge,
+}
+
+fn default_rebalance_enabled() -> bool {
+    true
+}
+
+fn default_rebalance_tolerance() -> f64 {
+    0.02
+}
+
+/// Split of capital between the pairs worth entering in a cycle and the caps
+/// on everything the bot holds, checked before any order of a new pair is
+/// signed, see `strategy::plan_entries` and `portfolio::check_limits`.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AllocationConfig {
+    /// Most pairs opened in one cycle, best net expected return first.
+    #[serde(default = "default_max_new_pairs")]
+    pub max_new_pairs: usize,
+    /// Largest notional in USD per leg of a single pair. Zero means no cap.
+    #[serde(default)]
+    pub max_market_exposure: f64,
+    /// Largest notional in USD per venue over all open pairs. Zero means no cap.
+    #[serde(default)]
+    pub max_total_exposure: f64,
+    /// Most pairs held at the same time. Zero means no cap.
+    #[serde(default)]
+    pub max_open_pairs: usize,
+    /// Largest share of each venue's equity locked as margin.
+    #[serde(default = "default_max_margin_utilization")]
+    pub max_margin_utilization: f64,
+}
+
+impl Default for AllocationConfig {
+    fn default() -> Self {
+        AllocationConfig {
+            max_new_pairs: default_max_new_pairs(),
+            max_market_exposure: 0.0,
+            max_total_exposure: 0.0,
+            max_open_pairs: 0,
+            max_margin_utilization: default_max_margin_utilization(),
+        }
+    }
+}
+
+fn default_max_new_pairs() -> usize {
+    3
+}
+
+fn default_max_margin_utilization() -> f64 {
+    1.0
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PairEntry {
+    pub extended: String,
+    pub pacifica: String,
+    pub buy_amount: Option<f64>,
+    pub funding_rate_threshold: Option<f64>,
+    pub price_spread_threshold: Option<f64>,
+    pub extended_slippage: Option<f64>,
+    pub pacifica_slippage: Option<f64>,
+    pub orphan_policy: Option<OrphanPolicy>,
+    pub entry_mode: Option<EntryMode>,
+    pub maker_timeout_secs: Option<u64>,
+    pub twap_slices: Option<u32>,
+    pub twap_window_secs: Option<u64>,
+    pub tp_sl: Option<TpSlOverride>,
+    pub expected_holding_hours: Option<f64>,
+    pub estimated_slippage: Option<f64>,
+    pub min_net_edge: Option<f64>,
+    pub exit: Option<ExitOverride>,
+    pub sizing: Option<SizingOverride>,
+}
+
+/// Validated configuration with the defaults applied to every pair.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub target_minute: u32,
+    pub extended: VenueHttpConfig,
+    pub pacifica: VenueHttpConfig,
+    pub defaults: PairDefaults,
+    pub discovery: DiscoveryConfig,
+    pub fees: FeeConfig,
+    pub dry_run: DryRunConfig,
+    pub rebalance: RebalanceConfig,
+    pub allocation: AllocationConfig,
+    pub pairs: Vec<MarketPair>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MarketPair {
+    pub extended: PairLeg,
+    pub pacifica: PairLeg,
+    /// Notional in USD opened on each leg, unless `sizing` sizes by equity.
+    pub buy_amount: f64,
+    /// Minimum funding rate difference, in percent per hour, needed to enter.
+    pub funding_rate_threshold: f64,
+    /// Maximum price spread between the venues, in percent, allowed to enter.
+    pub price_spread_threshold: f64,
+    pub orphan_policy: OrphanPolicy,
+    pub entry_mode: EntryMode,
+    /// How long a maker entry rests before the unfilled rest is cancelled.
+    pub maker_timeout_secs: u64,
+    /// Number of child pairs a TWAP entry is split into.
+    pub twap_slices: u32,
+    /// Time over which the TWAP children are spread.
+    pub twap_window_secs: u64,
+    pub tp_sl: TpSlPolicy,
+    /// Hours a position is expected to stay open, used to value the funding it earns.
+    pub expected_holding_hours: f64,
+    /// Expected price impact of each fill, as a fraction.
+    pub estimated_slippage: f64,
+    /// Net expected profit, in percent of `buy_amount`, needed to enter.
+    pub min_net_edge: f64,
+    pub exit: ExitPolicy,
+    pub sizing: SizingPolicy,
+}
+
+#[derive(Debug, Clone)]
+pub struct PairLeg {
+    pub market: String,
+    /// Maximum price deviation accepted on market orders, as a fraction.
+    pub slippage: f64,
+}
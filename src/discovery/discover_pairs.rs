@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+use crate::{
+    config::{load_config::pair_from_defaults, structs::Config},
+    discovery::structs::{DiscoveredPair, MarketKey},
+    extended::markets::get_markets::get_extended_markets,
+    pacifica::markets::get_markets::get_pacifica_markets,
+    utils::venue_client::VenueClient,
+};
+
+/// Lists the markets that can be traded on both Extended and Pacifica.
+pub async fn discover_pairs(
+    extended_client: &VenueClient,
+    pacifica_client: &VenueClient,
+) -> anyhow::Result<Vec<DiscoveredPair>> {
+    let extended_markets = get_extended_markets(extended_client)
+        .await?
+        .into_iter()
+        .filter(|market| market.status == "ACTIVE")
+        .map(|market| market.name)
+        .collect::<Vec<String>>();
+    let pacifica_markets = get_pacifica_markets(pacifica_client)
+        .await?
+        .into_iter()
+        .map(|market| market.symbol)
+        .collect::<Vec<String>>();
+
+    Ok(match_markets(&extended_markets, &pacifica_markets))
+}
+
+/// Pairs up markets that trade the same asset with the same contract size.
+pub fn match_markets(
+    extended_markets: &[String],
+    pacifica_markets: &[String],
+) -> Vec<DiscoveredPair> {
+    let pacifica_by_key = pacifica_markets
+        .iter()
+        .map(|symbol| (pacifica_market_key(symbol), symbol))
+        .collect::<HashMap<MarketKey, &String>>();
+
+    extended_markets
+        .iter()
+        .filter_map(|name| {
+            let key = extended_market_key(name)?;
+            pacifica_by_key.get(&key).map(|symbol| DiscoveredPair {
+                extended_market: name.to_string(),
+                pacifica_market: symbol.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// How many Extended contracts one Pacifica contract is worth: 1 for
+/// `1000BONK-USD` and `kBONK`, 0.001 for `1000BONK-USD` and `BONK`.
+pub fn contract_ratio(extended_market: &str, pacifica_market: &str) -> f64 {
+    let extended_multiplier = extended_market_key(extended_market)
+        .map(|key| key.multiplier)
+        .unwrap_or(1);
+
+    pacifica_market_key(pacifica_market).multiplier as f64 / extended_multiplier as f64
+}
+
+/// Extended lists USD quoted markets as `ETH-USD` and scaled ones as `1000BONK-USD`.
+pub fn extended_market_key(name: &str) -> Option<MarketKey> {
+    let asset = name.strip_suffix("-USD")?;
+
+    for (prefix, multiplier) in [("1000000", 1_000_000), ("1000", 1_000)] {
+        if let Some(base) = asset.strip_prefix(prefix)
+            && base.starts_with(|c: char| c.is_ascii_alphabetic())
+        {
+            return Some(MarketKey {
+                multiplier,
+                base: base.to_string(),
+            });
+        }
+    }
+
+    Some(MarketKey {
+        multiplier: 1,
+        base: asset.to_string(),
+    })
+}
+
+/// Pacifica lists markets by asset, with a lowercase `k` or `m` in front of
+/// scaled ones, e.g. `kBONK`.
+pub fn pacifica_market_key(symbol: &str) -> MarketKey {
+    for (prefix, multiplier) in [('k', 1_000), ('m', 1_000_000)] {
+        if let Some(base) = symbol.strip_prefix(prefix)
+            && base.starts_with(|c: char| c.is_ascii_uppercase())
+        {
+            return MarketKey {
+                multiplier,
+                base: base.to_string(),
+            };
+        }
+    }
+
+    MarketKey {
+        multiplier: 1,
+        base: symbol.to_string(),
+    }
+}
+
+/// Logs markets listed on both venues that are not configured yet and, when
+/// `auto_enable` is set, adds them to the traded pairs with the default settings.
+pub async fn apply_discovery(
+    config: &mut Config,
+    extended_client: &VenueClient,
+    pacifica_client: &VenueClient,
+) -> anyhow::Result<()> {
+    let discovered_pairs = discover_pairs(extended_client, pacifica_client).await?;
+
+    for discovered in discovered_pairs {
+        if config
+            .discovery
+            .exclude
+            .contains(&discovered.extended_market)
+        {
+            continue;
+        }
+
+        let is_configured = config.pairs.iter().any(|pair| {
+            pair.extended.market == discovered.extended_market
+                || pair.pacifica.market == discovered.pacifica_market
+        });
+        if is_configured {
+            continue;
+        }
+
+        if config.discovery.auto_enable {
+            println!(
+                "Discovery: enabling new pair {} / {}",
+                discovered.extended_market, discovered.pacifica_market
+            );
+            config.pairs.push(pair_from_defaults(
+                &discovered.extended_market,
+                &discovered.pacifica_market,
+                &config.defaults,
+            ));
+        } else {
+            println!(
+                "Discovery: proposed pair {} / {} (add it to the config to trade it)",
+                discovered.extended_market, discovered.pacifica_market
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,2 @@
+pub mod discover_pairs;
+pub mod structs;
@@ -0,0 +1,15 @@
+/// A market listed on both venues.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscoveredPair {
+    pub extended_market: String,
+    pub pacifica_market: String,
+}
+
+/// Underlying asset of a market together with the number of units one
+/// contract represents, e.g. `1000BONK-USD` and `kBONK` are both
+/// `(1000, "BONK")`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MarketKey {
+    pub multiplier: u64,
+    pub base: String,
+}
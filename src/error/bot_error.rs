@@ -0,0 +1,198 @@
+use std::fmt;
+
+use rust_decimal::{
+    Decimal,
+    prelude::{FromPrimitive, ToPrimitive},
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::sizing::structs::SizeLimit;
+
+/// Failures the main loop needs to tell apart: a network problem is worth
+/// retrying, a venue rejection needs attention and a skip is routine.
+#[derive(Debug, Error)]
+pub enum BotError {
+    #[error("Transport error: {0}")]
+    Transport(#[from] reqwest::Error),
+    #[error("HTTP {status}: {body}")]
+    HttpStatus { status: u16, body: String },
+    #[error(
+        "Rejected by venue{}: {message}{}",
+        code.as_ref().map(|c| format!(" (code {})", c)).unwrap_or_default(),
+        body.as_ref().map(|b| format!(", response: {}", b)).unwrap_or_default()
+    )]
+    Rejected {
+        code: Option<String>,
+        message: String,
+        /// Raw response body, kept so a rejected order can be investigated.
+        body: Option<String>,
+    },
+    #[error("Failed to parse {context}: {message}")]
+    Parse { context: String, message: String },
+    #[error("Skipped: {0}")]
+    Skip(SkipReason),
+}
+
+/// Why the strategy decided not to trade a market this cycle.
+#[derive(Debug, Clone)]
+pub enum SkipReason {
+    FundingRateDiffTooLow {
+        diff: f64,
+        threshold: f64,
+    },
+    PriceSpreadTooHigh {
+        spread: f64,
+        threshold: f64,
+    },
+    PositionTooSmall {
+        notional: f64,
+        minimum: f64,
+        limited_by: SizeLimit,
+    },
+    NetEdgeTooLow {
+        net: f64,
+        required: f64,
+    },
+    AlreadyHeld {
+        market: String,
+    },
+    MaxOpenPairs {
+        open: usize,
+        limit: usize,
+    },
+    /// A TWAP entry stopped sending slices, the ones already sent stay open.
+    TwapAborted {
+        sent: u32,
+        slices: u32,
+        spread: f64,
+        threshold: f64,
+    },
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipReason::FundingRateDiffTooLow { diff, threshold } => write!(
+                f,
+                "funding rate diff {} is below the threshold {}",
+                diff, threshold
+            ),
+            SkipReason::PriceSpreadTooHigh { spread, threshold } => write!(
+                f,
+                "price spread {} is above the threshold {}",
+                spread, threshold
+            ),
+            SkipReason::PositionTooSmall {
+                notional,
+                minimum,
+                limited_by,
+            } => write!(
+                f,
+                "position size {} limited by the {} is below the minimum {}",
+                notional, limited_by, minimum
+            ),
+            SkipReason::NetEdgeTooLow { net, required } => write!(
+                f,
+                "expected net profit {} is below the required {}",
+                net, required
+            ),
+            SkipReason::AlreadyHeld { market } => {
+                write!(f, "a position in {} is already held", market)
+            }
+            SkipReason::MaxOpenPairs { open, limit } => {
+                write!(f, "{} pairs are open, the limit is {}", open, limit)
+            }
+            SkipReason::TwapAborted {
+                sent,
+                slices,
+                spread,
+                threshold,
+            } => write!(
+                f,
+                "TWAP stopped after {} of {} slices, price spread {} is above the threshold {}",
+                sent, slices, spread, threshold
+            ),
+        }
+    }
+}
+
+impl BotError {
+    pub fn rejected(message: impl Into<String>) -> BotError {
+        BotError::Rejected {
+            code: None,
+            message: message.into(),
+            body: None,
+        }
+    }
+
+    pub fn parse(context: impl Into<String>, message: impl ToString) -> BotError {
+        BotError::Parse {
+            context: context.into(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Whether sending the same request again later can succeed.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            BotError::Transport(error) => {
+                error.is_timeout() || error.is_connect() || error.is_request()
+            }
+            BotError::HttpStatus { status, .. } => *status == 429 || *status >= 500,
+            _ => false,
+        }
+    }
+
+    /// Builds the error for a non-success HTTP response, recognizing the error
+    /// bodies Extended and Pacifica send when they reject a request.
+    pub fn from_response(status: u16, body: String) -> BotError {
+        match serde_json::from_str::<VenueErrorBody>(&body) {
+            Ok(VenueErrorBody::Extended { error }) => BotError::Rejected {
+                code: Some(error.code.to_string()),
+                message: error.message,
+                body: Some(body),
+            },
+            Ok(VenueErrorBody::Pacifica { error, code }) => BotError::Rejected {
+                code: code.map(|c| c.to_string()),
+                message: error,
+                body: Some(body),
+            },
+            Err(_) => BotError::HttpStatus { status, body },
+        }
+    }
+}
+
+/// Error payloads returned by the venues on rejected requests.
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum VenueErrorBody {
+    Extended { error: ExtendedErrorDetail },
+    Pacifica { error: String, code: Option<i64> },
+}
+
+#[derive(Deserialize, Debug)]
+struct ExtendedErrorDetail {
+    code: i64,
+    message: String,
+}
+
+/// Parses a numeric field sent by a venue as a string.
+pub fn parse_number(value: &str, field: &str) -> Result<f64, BotError> {
+    value
+        .parse::<f64>()
+        .map_err(|e| BotError::parse(field, format!("{:?}: {}", value, e)))
+}
+
+/// Converts an exact venue amount into the float the strategy works with.
+pub fn decimal_to_f64(value: Decimal, field: &str) -> Result<f64, BotError> {
+    value
+        .to_f64()
+        .ok_or_else(|| BotError::parse(field, format!("{} does not fit in f64", value)))
+}
+
+/// Converts a strategy amount into an exact decimal before it reaches a venue.
+pub fn f64_to_decimal(value: f64, field: &str) -> Result<Decimal, BotError> {
+    Decimal::from_f64(value)
+        .ok_or_else(|| BotError::parse(field, format!("{} is not a valid decimal", value)))
+}
@@ -1,22 +1,21 @@
-use anyhow::anyhow;
+use crate::{
+    error::bot_error::BotError,
+    extended::structs::{TradeableBalance, TradeableBalanceData},
+    utils::venue_client::VenueClient,
+};
 
-use crate::extended::structs::{TradeableBalance, TradeableBalanceData};
+pub async fn get_extended_tradeable_balance(
+    client: &VenueClient,
+    api_key: &str,
+) -> anyhow::Result<TradeableBalanceData> {
+    let url = client.url("/api/v1/user/balance");
 
-pub async fn get_extended_tradeable_balance(api_key: &str) -> anyhow::Result<TradeableBalanceData> {
-    let url = String::from("https://api.starknet.extended.exchange/api/v1/user/balance");
-
-    let client = reqwest::Client::new();
     let tradeable_balance_data = client
-         .get(&url)
-         .header("User-Agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-         .header("X-Api-Key",  api_key)
-         .send()
-         .await?
-         .json::<TradeableBalance>()
-         .await?;
+        .send_json::<TradeableBalance>(client.http.get(&url).header("X-Api-Key", api_key))
+        .await?;
 
     if tradeable_balance_data.status.eq("ERROR") {
-        return Err(anyhow!("Failed to get tradeable balance"));
+        return Err(BotError::rejected("Failed to get tradeable balance").into());
     }
 
     Ok(tradeable_balance_data.data)
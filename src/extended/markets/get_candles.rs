@@ -0,0 +1,30 @@
+use crate::{
+    error::bot_error::BotError,
+    extended::structs::{CandleData, CandlesResponse},
+    utils::venue_client::VenueClient,
+};
+
+/// Last `limit` hourly trade candles of the market, oldest first.
+pub async fn get_extended_candles(
+    client: &VenueClient,
+    market_name: &str,
+    limit: usize,
+) -> anyhow::Result<Vec<CandleData>> {
+    let url = client.url(&format!(
+        "/api/v1/info/candles/{}/trades?interval=PT1H&limit={}",
+        market_name, limit
+    ));
+
+    let candles = client
+        .send_json::<CandlesResponse>(client.http.get(&url))
+        .await?;
+
+    if candles.status.eq("ERROR") {
+        return Err(BotError::rejected("Invalid Candle Data").into());
+    }
+
+    let mut candles = candles.data;
+    candles.sort_by_key(|candle| candle.timestamp);
+
+    Ok(candles)
+}
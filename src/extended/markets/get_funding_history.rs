@@ -0,0 +1,32 @@
+use crate::{
+    error::bot_error::BotError,
+    extended::structs::{FundingHistoryResponse, FundingRateData},
+    utils::venue_client::VenueClient,
+};
+
+/// Funding rates the market paid between `start_millis` and `end_millis`,
+/// oldest first.
+pub async fn get_extended_funding_history(
+    client: &VenueClient,
+    market_name: &str,
+    start_millis: u64,
+    end_millis: u64,
+) -> anyhow::Result<Vec<FundingRateData>> {
+    let url = client.url(&format!(
+        "/api/v1/info/{}/funding?startTime={}&endTime={}",
+        market_name, start_millis, end_millis
+    ));
+
+    let history = client
+        .send_json::<FundingHistoryResponse>(client.http.get(&url))
+        .await?;
+
+    if history.status.eq("ERROR") {
+        return Err(BotError::rejected("Invalid Funding History").into());
+    }
+
+    let mut rates = history.data;
+    rates.sort_by_key(|rate| rate.timestamp);
+
+    Ok(rates)
+}
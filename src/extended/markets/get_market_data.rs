@@ -1,25 +1,21 @@
-use anyhow::anyhow;
+use crate::{
+    error::bot_error::BotError,
+    extended::structs::{MarketInfo, MarketInfoData},
+    utils::venue_client::VenueClient,
+};
 
-use crate::extended::structs::{MarketInfo, MarketInfoData};
+pub async fn get_extended_market_data(
+    client: &VenueClient,
+    market_name: &str,
+) -> anyhow::Result<Vec<MarketInfoData>> {
+    let url = client.url(&format!("/api/v1/info/markets?market={}", market_name));
 
-pub async fn get_extended_market_data(market_name: &str) -> anyhow::Result<Vec<MarketInfoData>> {
-    let url = format!(
-        "https://api.starknet.extended.exchange/api/v1/info/markets?market={}",
-        market_name
-    );
-
-    // Create a client with browser-like headers
-    let client = reqwest::Client::new();
     let market_data = client
-        .get(&url)
-        .header("User-Agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-        .send()
-        .await?
-        .json::<MarketInfo>()
+        .send_json::<MarketInfo>(client.http.get(&url))
         .await?;
 
     if market_data.status.eq("ERROR") || market_data.data.len() == 0 {
-        return Err(anyhow!("Invalid Market Data"));
+        return Err(BotError::rejected("Invalid Market Data").into());
     }
 
     Ok(market_data.data)
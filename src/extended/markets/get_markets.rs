@@ -0,0 +1,20 @@
+use crate::{
+    error::bot_error::BotError,
+    extended::structs::{MarketInfo, MarketInfoData},
+    utils::venue_client::VenueClient,
+};
+
+/// Lists every market available on Extended.
+pub async fn get_extended_markets(client: &VenueClient) -> anyhow::Result<Vec<MarketInfoData>> {
+    let url = client.url("/api/v1/info/markets");
+
+    let market_data = client
+        .send_json::<MarketInfo>(client.http.get(&url))
+        .await?;
+
+    if market_data.status.eq("ERROR") {
+        return Err(BotError::rejected("Invalid Market Data").into());
+    }
+
+    Ok(market_data.data)
+}
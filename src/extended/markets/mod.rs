@@ -1 +1,4 @@
+pub mod get_candles;
+pub mod get_funding_history;
 pub mod get_market_data;
+pub mod get_markets;
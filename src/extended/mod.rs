@@ -2,3 +2,4 @@ pub mod account;
 pub mod markets;
 pub mod orders;
 pub mod structs;
+pub mod venue;
@@ -0,0 +1,54 @@
+use crate::{
+    error::bot_error::BotError, extended::structs::CancelOrderResponse,
+    utils::venue_client::VenueClient,
+};
+
+/// Cancels an order by the id Extended assigned to it.
+pub async fn cancel_extended_order(
+    client: &VenueClient,
+    order_id: &str,
+    api_key: &str,
+) -> anyhow::Result<()> {
+    let url = client.url(&format!("/api/v1/user/order/{}", order_id));
+
+    let cancel_order_data = client
+        .send_json::<CancelOrderResponse>(client.http.delete(&url).header("X-Api-Key", api_key))
+        .await?;
+
+    check_cancel_response(cancel_order_data)
+}
+
+/// Cancels an order by the external id it was placed with.
+pub async fn cancel_extended_order_by_external_id(
+    client: &VenueClient,
+    external_id: &str,
+    api_key: &str,
+) -> anyhow::Result<()> {
+    let cancel_order_data = client
+        .send_json::<CancelOrderResponse>(
+            client
+                .http
+                .delete(client.url("/api/v1/user/order"))
+                .query(&[("externalId", external_id)])
+                .header("X-Api-Key", api_key),
+        )
+        .await?;
+
+    check_cancel_response(cancel_order_data)
+}
+
+pub fn check_cancel_response(response: CancelOrderResponse) -> anyhow::Result<()> {
+    if response.status.eq("ERROR") {
+        return Err(match response.error {
+            Some(error) => BotError::Rejected {
+                code: Some(error.code.to_string()),
+                message: error.message,
+                body: None,
+            },
+            None => BotError::rejected("Failed to cancel order"),
+        }
+        .into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use crate::{
+    error::bot_error::BotError,
+    extended::structs::{OpenOrderData, OpenOrdersResponse},
+    utils::venue_client::VenueClient,
+};
+
+/// Lists the orders still open on `market_name`, or on every market when it is
+/// `None`.
+pub async fn get_extended_open_orders(
+    client: &VenueClient,
+    market_name: Option<&str>,
+    api_key: &str,
+) -> anyhow::Result<Vec<OpenOrderData>> {
+    let mut request = client
+        .http
+        .get(client.url("/api/v1/user/orders"))
+        .header("X-Api-Key", api_key);
+    if let Some(market_name) = market_name {
+        request = request.query(&[("market", market_name)]);
+    }
+
+    let open_orders = client.send_json::<OpenOrdersResponse>(request).await?;
+
+    if let Some(error) = open_orders.error {
+        return Err(BotError::Rejected {
+            code: Some(error.code.to_string()),
+            message: error.message,
+            body: None,
+        }
+        .into());
+    }
+
+    Ok(open_orders.data)
+}
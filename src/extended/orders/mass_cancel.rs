@@ -0,0 +1,26 @@
+use crate::{
+    extended::{
+        orders::cancel_order::check_cancel_response,
+        structs::{CancelOrderResponse, MassCancelRequest},
+    },
+    utils::venue_client::VenueClient,
+};
+
+/// Cancels every open order matching the request, TP/SL orders included.
+pub async fn mass_cancel_extended_orders(
+    client: &VenueClient,
+    request: &MassCancelRequest,
+    api_key: &str,
+) -> anyhow::Result<()> {
+    let cancel_order_data = client
+        .send_json::<CancelOrderResponse>(
+            client
+                .http
+                .post(client.url("/api/v1/user/order/massCancel"))
+                .header("X-Api-Key", api_key)
+                .json(request),
+        )
+        .await?;
+
+    check_cancel_response(cancel_order_data)
+}
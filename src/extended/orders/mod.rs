@@ -1 +1,6 @@
+pub mod cancel_order;
+pub mod get_open_orders;
+pub mod mass_cancel;
+pub mod place_limit_order;
 pub mod place_order;
+pub mod place_tp_sl;
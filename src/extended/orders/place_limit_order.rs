@@ -0,0 +1,80 @@
+use rust_decimal::Decimal;
+
+use crate::{
+    error::bot_error::BotError,
+    extended::{
+        orders::place_order::{
+            create_order, create_order_context, get_fees, get_starknet_domain,
+            submit_extended_order,
+        },
+        structs::{MarketInfoData, OrderType, Side},
+    },
+    utils::{
+        utils::{RoundingMode, round_to_min_change},
+        venue_client::VenueClient,
+    },
+    venue::structs::{OrderAck, OrderIntent, TpSl},
+};
+
+/// Rests a post-only limit order at `price`. It pays the maker fee and is
+/// rejected by the venue instead of crossing the book. The take profit and
+/// stop loss of `tp_sl` are measured from `price`.
+pub async fn place_extended_limit_order(
+    client: &VenueClient,
+    market_name: &str,
+    market: &MarketInfoData,
+    side: Side,
+    qty: Decimal,
+    price: Decimal,
+    tp_sl: Option<TpSl>,
+    api_key: &str,
+    stark_private_key: &str,
+    vault_id: &str,
+    stark_public_key: &str,
+) -> anyhow::Result<OrderAck> {
+    let fees_vec = get_fees(client, market_name, api_key).await?;
+    let fees = fees_vec
+        .first()
+        .ok_or_else(|| BotError::rejected("Failed to get fees"))?;
+
+    let starknet_domain = get_starknet_domain(client).await?;
+    let ctx = create_order_context(
+        market,
+        fees.maker_fee_rate,
+        starknet_domain,
+        vault_id,
+        stark_private_key,
+    )
+    .await;
+
+    // Round away from the book so the order never crosses the spread
+    let price_rounding = if matches!(side, Side::Buy) {
+        RoundingMode::Floor
+    } else {
+        RoundingMode::Ceil
+    };
+    let price = round_to_min_change(
+        price,
+        market.trading_config.min_price_change,
+        Some(price_rounding),
+    );
+
+    let place_order = create_order(
+        market_name,
+        side,
+        &round_to_min_change(
+            qty,
+            market.trading_config.min_order_size_change,
+            Some(RoundingMode::Floor),
+        ),
+        &price,
+        &ctx,
+        OrderType::PostOnlyLimit,
+        price,
+        OrderIntent::Open(tp_sl),
+        stark_public_key,
+    )
+    .await?;
+
+    submit_extended_order(client, &place_order, api_key).await
+}
@@ -1,138 +1,157 @@
 use crate::{
+    error::bot_error::BotError,
     extended::structs::{StopLoss, TakeProfit},
-    utils::utils::{RoundingMode, calc_entire_position_size, round_to_min_change_f64},
+    utils::{
+        utils::{RoundingMode, round_to_min_change},
+        venue_client::VenueClient,
+    },
+    venue::structs::{OrderAck, OrderIntent, OrderStatus},
 };
-use reqwest::Client;
+use rust_decimal::{Decimal, prelude::ToPrimitive};
 use starknet::core::types::Felt;
-use std::ops::{Add, Mul};
+use std::ops::Add;
 
 use crate::extended::structs::{
     CreateOrderParams, DebuggingAmounts, FeeResponse, FeeResponseData, MarketInfoData,
-    OrderContext, PlaceOrder, Settlement, Side, Signature, StarknetDomain, StarknetDomainData,
+    OrderContext, OrderType, PlaceOrder, PlaceOrderResponse, Settlement, Side, Signature,
+    StarknetDomain, StarknetDomainData,
 };
 
-const SLIPPAGE: f64 = 0.01;
 const STARKNET_SETTLEMENT_BUFFER_SECONDS: u64 = 14 * 24 * 60 * 60;
 const MILLIS_IN_SECOND: u64 = 1_000;
 
-pub async fn place_extended_order(
+/// Builds and signs a market order without sending it, so it can be submitted
+/// together with the other leg.
+pub async fn prepare_extended_order(
+    client: &VenueClient,
     market_name: &str,
     market: &MarketInfoData,
     side: Side,
-    qty: f64,
-    tp_sl_included: bool,
+    qty: Decimal,
+    slippage: Decimal,
+    intent: OrderIntent,
     api_key: &str,
     stark_private_key: &str,
     vault_id: &str,
     stark_public_key: &str,
-) -> anyhow::Result<()> {
-    let client = reqwest::Client::new();
-
-    let fees_vec = get_fees(&client, market_name, &api_key).await?;
+) -> anyhow::Result<PlaceOrder> {
+    let fees_vec = get_fees(client, market_name, &api_key).await?;
     let fees = fees_vec.first().unwrap();
 
     let order_price = if matches!(side, Side::Buy) {
-        market
-            .market_stats
-            .bid_price
-            .parse::<f64>()
-            .unwrap()
-            .mul(1.0 + SLIPPAGE)
+        market.market_stats.bid_price * (Decimal::ONE + slippage)
     } else {
-        market
-            .market_stats
-            .bid_price
-            .parse::<f64>()
-            .unwrap()
-            .mul(1.0 - SLIPPAGE)
+        market.market_stats.bid_price * (Decimal::ONE - slippage)
     };
 
-    let starknet_domain = get_starknet_domain(&client).await?;
-    let ctx =
-        create_order_context(market, fees, starknet_domain, &vault_id, &stark_private_key).await;
+    let starknet_domain = get_starknet_domain(client).await?;
+    let ctx = create_order_context(
+        market,
+        fees.taker_fee_rate,
+        starknet_domain,
+        &vault_id,
+        &stark_private_key,
+    )
+    .await;
 
-    let place_order = create_order(
+    create_order(
         market_name,
         side,
-        &round_to_min_change_f64(
+        &round_to_min_change(
             qty,
-            market
-                .trading_config
-                .min_order_size_change
-                .parse::<f64>()
-                .unwrap(),
+            market.trading_config.min_order_size_change,
             Some(RoundingMode::Floor),
         ),
-        &round_to_min_change_f64(
+        &round_to_min_change(
             order_price,
-            market
-                .trading_config
-                .min_price_change
-                .parse::<f64>()
-                .unwrap(),
+            market.trading_config.min_price_change,
             Some(RoundingMode::Floor),
         ),
         &ctx,
-        order_price,
-        tp_sl_included,
+        OrderType::Market,
+        // Mid price, like Pacifica, so both legs get the same protective levels
+        (market.market_stats.bid_price + market.market_stats.ask_price) / Decimal::TWO,
+        intent,
         stark_public_key,
     )
-    .await?;
+    .await
+}
 
+/// Sends an order built by `prepare_extended_order` or `create_order`.
+pub async fn submit_extended_order(
+    client: &VenueClient,
+    place_order: &PlaceOrder,
+    api_key: &str,
+) -> anyhow::Result<OrderAck> {
+    let client_order_id = place_order.id.to_string();
     let response = client
-        .post("https://api.starknet.extended.exchange/api/v1/user/order")
-        .json(&place_order)
-        .header("User-Agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-        .header("X-Api-Key", api_key)
-        .send()
-        .await?
-        .text()
+        .send_text(
+            client
+                .http
+                .post(client.url("/api/v1/user/order"))
+                .json(place_order)
+                .header("X-Api-Key", api_key),
+        )
         .await?;
 
-    println!("Response: {}", response);
+    Ok(parse_order_ack(client_order_id, response)?)
+}
 
-    if response.contains("ERROR") {
-        return Err(anyhow::anyhow!("Failed to place order"));
-    }
+pub fn parse_order_ack(client_order_id: String, body: String) -> Result<OrderAck, BotError> {
+    let response = serde_json::from_str::<PlaceOrderResponse>(&body)
+        .map_err(|e| BotError::parse("PlaceOrderResponse", format!("{}: {}", e, body)))?;
+
+    let ack = if response.status.eq("OK") {
+        OrderAck {
+            venue_order_id: response.data.map(|data| data.id.to_string()),
+            client_order_id,
+            status: OrderStatus::Accepted,
+            rejection_reason: None,
+            body,
+        }
+    } else {
+        OrderAck {
+            venue_order_id: None,
+            client_order_id,
+            status: OrderStatus::Rejected,
+            rejection_reason: response
+                .error
+                .map(|error| format!("{} (code {})", error.message, error.code)),
+            body,
+        }
+    };
 
-    Ok(())
+    ack.accepted()
 }
 
 pub async fn get_fees(
-    client: &Client,
+    client: &VenueClient,
     market_name: &str,
     api_key: &str,
 ) -> anyhow::Result<Vec<FeeResponseData>> {
     let fee_response = client
-        .get(&format!(
-            "https://api.starknet.extended.exchange/api/v1/user/fees?market={}",
-            market_name
-        ))
-        .header("User-Agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-        .header("X-Api-Key", api_key)
-        .send()
-        .await?
-        .json::<FeeResponse>()
+        .send_json::<FeeResponse>(
+            client
+                .http
+                .get(client.url(&format!("/api/v1/user/fees?market={}", market_name)))
+                .header("X-Api-Key", api_key),
+        )
         .await?;
 
     if fee_response.status.eq("ERROR") || fee_response.data.len() == 0 {
-        return Err(anyhow::anyhow!("Failed to get fees"));
+        return Err(BotError::rejected("Failed to get fees").into());
     }
 
     Ok(fee_response.data)
 }
 
-pub async fn get_starknet_domain(client: &Client) -> anyhow::Result<StarknetDomainData> {
+pub async fn get_starknet_domain(client: &VenueClient) -> anyhow::Result<StarknetDomainData> {
     let starknet_domain_response = client
-        .get("https://api.starknet.extended.exchange/api/v1/info/starknet")
-        .header("User-Agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-        .send()
-        .await?
-        .json::<StarknetDomain>()
+        .send_json::<StarknetDomain>(client.http.get(client.url("/api/v1/info/starknet")))
         .await?;
 
     if starknet_domain_response.status.eq("ERROR") {
-        return Err(anyhow::anyhow!("Failed to get starknet domain"));
+        return Err(BotError::rejected("Failed to get starknet domain").into());
     }
 
     Ok(starknet_domain_response.data)
@@ -140,7 +159,7 @@ pub async fn get_starknet_domain(client: &Client) -> anyhow::Result<StarknetDoma
 
 pub async fn create_order_context(
     market: &MarketInfoData,
-    fees: &FeeResponseData,
+    fee_rate: Decimal,
     starknet_domain: StarknetDomainData,
     vault_id: &str,
     stark_private_key: &str,
@@ -148,95 +167,48 @@ pub async fn create_order_context(
     OrderContext {
         asset_id_collateral: market.l2_config.collateral_id.to_string(),
         asset_id_synthetic: market.l2_config.synthetic_id.to_string(),
-        settlement_resolution_collateral: market.l2_config.collateral_resolution.to_string(),
-        settlement_resolution_synthetic: market.l2_config.synthetic_resolution.to_string(),
-        min_order_size_change: market.trading_config.min_order_size_change.to_string(),
-        min_price_change: market.trading_config.min_price_change.to_string(),
-        max_position_value: market.trading_config.max_position_value.to_string(),
-        fee_rate: fees.taker_fee_rate.to_string(),
+        settlement_resolution_collateral: Decimal::from(market.l2_config.collateral_resolution),
+        settlement_resolution_synthetic: Decimal::from(market.l2_config.synthetic_resolution),
+        min_order_size_change: market.trading_config.min_order_size_change,
+        min_price_change: market.trading_config.min_price_change,
+        max_position_value: market.trading_config.max_position_value,
+        fee_rate,
         vault_id: vault_id.to_string(),
         stark_private_key: stark_private_key.to_string(),
         starknet_domain: starknet_domain,
     }
 }
 
+/// Builds and signs an order, reduce-only when `intent` reduces a position.
+/// The take profit and stop loss distances of an opening `intent` are measured
+/// from `reference_price`.
 pub async fn create_order(
     market_name: &str,
     side: Side,
-    qty: &f64,
-    price: &f64,
+    qty: &Decimal,
+    price: &Decimal,
     ctx: &OrderContext,
-    normal_price: f64,
-    tp_sl_included: bool,
+    order_type: OrderType,
+    reference_price: Decimal,
+    intent: OrderIntent,
     stark_public_key: &str,
 ) -> Result<PlaceOrder, anyhow::Error> {
     let nonce = rand::random_range(0..u32::MAX);
     let expiry_epoch_millis = chrono::Utc::now().timestamp_millis() as u64 + 1000 * 60 * 60;
 
     let is_buying = matches!(&side, &Side::Buy);
+    let reduce_only = intent == OrderIntent::Reduce;
 
-    if tp_sl_included {
-        let rounding_mode = if is_buying {
-            RoundingMode::Floor
-        } else {
-            RoundingMode::Ceil
-        };
-        let min_price_change = ctx.min_price_change.parse::<f64>().unwrap();
-
-        let tp_trigger_price = round_to_min_change_f64(
-            if is_buying {
-                normal_price * 1.05
-            } else {
-                normal_price * 0.95
-            },
-            min_price_change,
-            Some(rounding_mode),
-        );
-        let tp_price = round_to_min_change_f64(
-            if is_buying {
-                normal_price * 1.045
-            } else {
-                normal_price * 0.965
-            },
-            min_price_change,
-            Some(rounding_mode),
-        );
-        let sl_trigger_price = round_to_min_change_f64(
-            if is_buying {
-                normal_price * 0.95
-            } else {
-                normal_price * 1.05
-            },
-            min_price_change,
-            Some(rounding_mode),
-        );
-        let sl_price = round_to_min_change_f64(
-            if is_buying {
-                normal_price * 0.945
-            } else {
-                normal_price * 1.055
-            },
-            min_price_change,
-            Some(rounding_mode),
-        );
-
-        let tp_amount_of_synthetic = calc_entire_position_size(
-            &tp_price,
-            &ctx.min_order_size_change.parse::<f64>().unwrap(),
-            &ctx.max_position_value.parse::<f64>().unwrap(),
-        );
-        let sl_amount_of_synthetic = calc_entire_position_size(
-            &sl_price,
-            &ctx.min_order_size_change.parse::<f64>().unwrap(),
-            &ctx.max_position_value.parse::<f64>().unwrap(),
-        );
+    if let Some(tp_sl) = intent.tp_sl() {
+        let prices = tp_sl.prices(is_buying, reference_price, ctx.min_price_change)?;
 
+        // Each protective order closes exactly the quantity this order opens
         let create_tp_order_params = get_create_order_params(
-            &tp_amount_of_synthetic,
-            &tp_price,
+            qty,
+            &prices.take_profit_limit,
             &expiry_epoch_millis,
             &nonce,
-            &ctx.fee_rate.parse::<f64>().unwrap(),
+            &ctx.fee_rate,
             ctx,
             !is_buying,
             stark_public_key,
@@ -244,23 +216,22 @@ pub async fn create_order(
         .await?;
 
         let create_sl_order_params = get_create_order_params(
-            &sl_amount_of_synthetic,
-            &sl_price,
+            qty,
+            &prices.stop_loss_limit,
             &expiry_epoch_millis,
             &nonce,
-            &ctx.fee_rate.parse::<f64>().unwrap(),
+            &ctx.fee_rate,
             ctx,
             !is_buying,
             stark_public_key,
         )
         .await?;
-
         let create_order_params = get_create_order_params(
             &qty,
             price,
             &expiry_epoch_millis,
             &nonce,
-            &ctx.fee_rate.parse::<f64>().unwrap(),
+            &ctx.fee_rate,
             ctx,
             is_buying,
             stark_public_key,
@@ -272,29 +243,29 @@ pub async fn create_order(
             market: market_name.to_string(),
             side: side,
             qty: qty.to_string(),
-            r#type: "MARKET".to_string(),
+            r#type: order_type.type_name().to_string(),
             price: price.to_string(),
-            reduce_only: false,
-            post_only: false,
-            time_in_force: "IOC".to_string(),
+            reduce_only,
+            post_only: order_type.is_post_only(),
+            time_in_force: order_type.time_in_force().to_string(),
             expiry_epoch_millis: expiry_epoch_millis,
             fee: ctx.fee_rate.to_string(),
             nonce: nonce.to_string(),
             settlement: create_order_params.order_signature,
             debugging_amounts: create_order_params.debug_amounts,
-            tp_sl_type: Some("POSITION".to_string()),
+            tp_sl_type: Some("ORDER".to_string()),
             take_profit: Some(TakeProfit {
-                trigger_price: tp_trigger_price.to_string(),
+                trigger_price: prices.take_profit_trigger.to_string(),
                 trigger_price_type: "LAST".to_string(),
-                price: tp_price.to_string(),
+                price: prices.take_profit_limit.to_string(),
                 price_type: "MARKET".to_string(),
                 settlement: create_tp_order_params.order_signature,
                 debugging_amounts: create_tp_order_params.debug_amounts,
             }),
             stop_loss: Some(StopLoss {
-                trigger_price: sl_trigger_price.to_string(),
+                trigger_price: prices.stop_loss_trigger.to_string(),
                 trigger_price_type: "LAST".to_string(),
-                price: sl_price.to_string(),
+                price: prices.stop_loss_limit.to_string(),
                 price_type: "MARKET".to_string(),
                 settlement: create_sl_order_params.order_signature,
                 debugging_amounts: create_sl_order_params.debug_amounts,
@@ -306,7 +277,7 @@ pub async fn create_order(
             price,
             &expiry_epoch_millis,
             &nonce,
-            &ctx.fee_rate.parse::<f64>().unwrap(),
+            &ctx.fee_rate,
             ctx,
             is_buying,
             stark_public_key,
@@ -318,11 +289,11 @@ pub async fn create_order(
             market: market_name.to_string(),
             side: side,
             qty: qty.to_string(),
-            r#type: "MARKET".to_string(),
+            r#type: order_type.type_name().to_string(),
             price: price.to_string(),
-            reduce_only: false,
-            post_only: false,
-            time_in_force: "IOC".to_string(),
+            reduce_only,
+            post_only: order_type.is_post_only(),
+            time_in_force: order_type.time_in_force().to_string(),
             expiry_epoch_millis: expiry_epoch_millis,
             fee: ctx.fee_rate.to_string(),
             nonce: nonce.to_string(),
@@ -336,40 +307,27 @@ pub async fn create_order(
 }
 
 pub async fn get_create_order_params(
-    amount_of_synthetic: &f64,
-    price: &f64,
+    amount_of_synthetic: &Decimal,
+    price: &Decimal,
     expiry_epoch_millis: &u64,
     nonce: &u32,
-    total_fee_rate: &f64,
+    total_fee_rate: &Decimal,
     ctx: &OrderContext,
     is_buying: bool,
     stark_public_key: &str,
 ) -> Result<CreateOrderParams, anyhow::Error> {
-    let collateral_amount = amount_of_synthetic.mul(price);
-    let fee = total_fee_rate.mul(collateral_amount);
+    let collateral_amount = amount_of_synthetic * price;
+    let fee = total_fee_rate * collateral_amount;
 
-    let collateral_amount_stark = if is_buying {
-        collateral_amount
-            .mul(ctx.settlement_resolution_collateral.parse::<f64>().unwrap())
-            .ceil()
-    } else {
-        collateral_amount
-            .mul(ctx.settlement_resolution_collateral.parse::<f64>().unwrap())
-            .floor()
-    };
-
-    let fee_stark = fee
-        .mul(ctx.settlement_resolution_collateral.parse::<f64>().unwrap())
-        .ceil();
-    let synthetic_amount_stark = if is_buying {
-        amount_of_synthetic
-            .mul(ctx.settlement_resolution_synthetic.parse::<f64>().unwrap())
-            .ceil()
-    } else {
-        amount_of_synthetic
-            .mul(ctx.settlement_resolution_synthetic.parse::<f64>().unwrap())
-            .floor()
-    };
+    let collateral_amount_stark = to_stark_amount(
+        collateral_amount * ctx.settlement_resolution_collateral,
+        is_buying,
+    )?;
+    let fee_stark = to_stark_amount(fee * ctx.settlement_resolution_collateral, true)?;
+    let synthetic_amount_stark = to_stark_amount(
+        amount_of_synthetic * ctx.settlement_resolution_synthetic,
+        is_buying,
+    )?;
 
     let order_hash = get_starknet_order_msg_hash(
         nonce,
@@ -405,13 +363,27 @@ pub async fn get_create_order_params(
     })
 }
 
+/// Converts an amount already scaled by the settlement resolution into the
+/// integer Stark amount, rounding in the venue's favour.
+pub fn to_stark_amount(scaled_amount: Decimal, round_up: bool) -> anyhow::Result<i64> {
+    let rounded = if round_up {
+        scaled_amount.ceil()
+    } else {
+        scaled_amount.floor()
+    };
+
+    rounded
+        .to_i64()
+        .ok_or_else(|| anyhow::anyhow!("Stark amount {} is out of range", rounded))
+}
+
 pub async fn get_starknet_order_msg_hash(
     nonce: &u32,
     asset_id_collateral: &str,
     asset_id_synthetic: &str,
-    collateral_amount_stark: &f64,
-    fee_stark: &f64,
-    synthetic_amount_stark: &f64,
+    collateral_amount_stark: &i64,
+    fee_stark: &i64,
+    synthetic_amount_stark: &i64,
     expiry_epoch_millis: &u64,
     vault_id: &str,
     stark_public_key: &str,
@@ -423,15 +395,15 @@ pub async fn get_starknet_order_msg_hash(
         .add(STARKNET_SETTLEMENT_BUFFER_SECONDS);
 
     let amount_collateral = if is_buying_synthetic {
-        &collateral_amount_stark.mul(-1.0)
+        -collateral_amount_stark
     } else {
-        collateral_amount_stark
+        *collateral_amount_stark
     };
 
     let amount_synthetic = if is_buying_synthetic {
-        synthetic_amount_stark
+        *synthetic_amount_stark
     } else {
-        &synthetic_amount_stark.mul(-1.0)
+        -synthetic_amount_stark
     };
 
     let wasm_hash: Result<Felt, String> = rust_crypto_lib_base::get_order_hash(
@@ -0,0 +1,136 @@
+use rust_decimal::Decimal;
+
+use crate::{
+    error::bot_error::BotError,
+    extended::{
+        orders::place_order::{
+            create_order_context, get_create_order_params, get_fees, get_starknet_domain,
+            submit_extended_order,
+        },
+        structs::{MarketInfoData, OrderType, PlaceOrder, Side, StopLoss, TakeProfit},
+    },
+    utils::{
+        utils::{RoundingMode, round_to_min_change},
+        venue_client::VenueClient,
+    },
+    venue::structs::{OrderAck, TpSl},
+};
+
+/// Protective orders stay on the book for as long as a pair is usually held.
+const TP_SL_EXPIRY_MILLIS: u64 = 28 * 24 * 60 * 60 * 1000;
+
+/// Places a reduce-only TPSL order closing `qty` of a position opened with
+/// `side`. The distances of `tp_sl` are measured from the mid price, like on
+/// opening orders.
+pub async fn place_extended_tp_sl(
+    client: &VenueClient,
+    market_name: &str,
+    market: &MarketInfoData,
+    side: Side,
+    qty: Decimal,
+    tp_sl: TpSl,
+    api_key: &str,
+    stark_private_key: &str,
+    vault_id: &str,
+    stark_public_key: &str,
+) -> anyhow::Result<OrderAck> {
+    let fees_vec = get_fees(client, market_name, api_key).await?;
+    let fees = fees_vec
+        .first()
+        .ok_or_else(|| BotError::rejected("Failed to get fees"))?;
+
+    let starknet_domain = get_starknet_domain(client).await?;
+    // Triggered orders take liquidity
+    let ctx = create_order_context(
+        market,
+        fees.taker_fee_rate,
+        starknet_domain,
+        vault_id,
+        stark_private_key,
+    )
+    .await;
+
+    let is_buying = matches!(side, Side::Buy);
+    let closing_side = if is_buying { Side::Sell } else { Side::Buy };
+    let qty = round_to_min_change(
+        qty,
+        market.trading_config.min_order_size_change,
+        Some(RoundingMode::Floor),
+    );
+    let mid = (market.market_stats.bid_price + market.market_stats.ask_price) / Decimal::TWO;
+    let prices = tp_sl.prices(is_buying, mid, ctx.min_price_change)?;
+
+    let nonce = rand::random_range(0..u32::MAX);
+    let expiry_epoch_millis = chrono::Utc::now().timestamp_millis() as u64 + TP_SL_EXPIRY_MILLIS;
+
+    let create_tp_order_params = get_create_order_params(
+        &qty,
+        &prices.take_profit_limit,
+        &expiry_epoch_millis,
+        &nonce,
+        &ctx.fee_rate,
+        &ctx,
+        !is_buying,
+        stark_public_key,
+    )
+    .await?;
+    let create_sl_order_params = get_create_order_params(
+        &qty,
+        &prices.stop_loss_limit,
+        &expiry_epoch_millis,
+        &nonce,
+        &ctx.fee_rate,
+        &ctx,
+        !is_buying,
+        stark_public_key,
+    )
+    .await?;
+    // The TPSL order itself is signed at the worst price it can close at
+    let create_order_params = get_create_order_params(
+        &qty,
+        &prices.stop_loss_limit,
+        &expiry_epoch_millis,
+        &nonce,
+        &ctx.fee_rate,
+        &ctx,
+        !is_buying,
+        stark_public_key,
+    )
+    .await?;
+
+    let place_order = PlaceOrder {
+        id: create_order_params.order_hash,
+        market: market_name.to_string(),
+        side: closing_side,
+        qty: qty.to_string(),
+        r#type: OrderType::TpSl.type_name().to_string(),
+        price: prices.stop_loss_limit.to_string(),
+        reduce_only: true,
+        post_only: OrderType::TpSl.is_post_only(),
+        time_in_force: OrderType::TpSl.time_in_force().to_string(),
+        expiry_epoch_millis,
+        fee: ctx.fee_rate.to_string(),
+        nonce: nonce.to_string(),
+        settlement: create_order_params.order_signature,
+        debugging_amounts: create_order_params.debug_amounts,
+        tp_sl_type: Some("ORDER".to_string()),
+        take_profit: Some(TakeProfit {
+            trigger_price: prices.take_profit_trigger.to_string(),
+            trigger_price_type: "LAST".to_string(),
+            price: prices.take_profit_limit.to_string(),
+            price_type: "MARKET".to_string(),
+            settlement: create_tp_order_params.order_signature,
+            debugging_amounts: create_tp_order_params.debug_amounts,
+        }),
+        stop_loss: Some(StopLoss {
+            trigger_price: prices.stop_loss_trigger.to_string(),
+            trigger_price_type: "LAST".to_string(),
+            price: prices.stop_loss_limit.to_string(),
+            price_type: "MARKET".to_string(),
+            settlement: create_sl_order_params.order_signature,
+            debugging_amounts: create_sl_order_params.debug_amounts,
+        }),
+    };
+
+    submit_extended_order(client, &place_order, api_key).await
+}
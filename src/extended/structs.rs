@@ -1,3 +1,4 @@
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Debug)]
@@ -10,28 +11,40 @@ pub struct MarketInfo {
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct MarketInfoData {
+    pub name: String,
+    pub status: String,
     pub market_stats: MarketStats,
     pub trading_config: TradingConfig,
     pub l2_config: L2Config,
+    /// Hours between funding payments. The markets endpoint does not publish
+    /// it, `ExtendedVenue` measures it from the funding history.
+    #[serde(skip)]
+    pub funding_interval_hours: Option<f64>,
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct MarketStats {
-    pub ask_price: String,
-    pub bid_price: String,
-    pub mark_price: String,
-    pub last_price: String,
-    pub index_price: String,
-    pub funding_rate: String,
+    pub ask_price: Decimal,
+    pub bid_price: Decimal,
+    pub mark_price: Decimal,
+    pub last_price: Decimal,
+    pub index_price: Decimal,
+    pub funding_rate: Decimal,
+    /// Rate projected for the coming funding payment.
+    #[serde(default)]
+    pub next_funding_rate: Option<Decimal>,
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct TradingConfig {
-    pub min_order_size_change: String,
-    pub max_position_value: String,
-    pub min_price_change: String,
+    pub min_order_size_change: Decimal,
+    pub max_position_value: Decimal,
+    pub min_price_change: Decimal,
+    /// Largest funding rate charged per period either way, when capped.
+    #[serde(default)]
+    pub max_funding_rate: Option<Decimal>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -108,6 +121,38 @@ pub enum Side {
     Sell,
 }
 
+/// How an order is executed once it reaches the book.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrderType {
+    /// Immediate-or-cancel order that takes liquidity.
+    Market,
+    /// Limit order that is rejected instead of taking liquidity.
+    PostOnlyLimit,
+    /// Conditional order that only carries a take profit and a stop loss.
+    TpSl,
+}
+
+impl OrderType {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            OrderType::Market => "MARKET",
+            OrderType::PostOnlyLimit => "LIMIT",
+            OrderType::TpSl => "TPSL",
+        }
+    }
+
+    pub fn time_in_force(&self) -> &'static str {
+        match self {
+            OrderType::Market => "IOC",
+            OrderType::PostOnlyLimit | OrderType::TpSl => "GTT",
+        }
+    }
+
+    pub fn is_post_only(&self) -> bool {
+        matches!(self, OrderType::PostOnlyLimit)
+    }
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateOrderParams {
@@ -172,9 +217,9 @@ pub struct FeeResponse {
 #[serde(rename_all = "camelCase")]
 pub struct FeeResponseData {
     pub market: String,
-    pub maker_fee_rate: String,
-    pub taker_fee_rate: String,
-    pub builder_fee_rate: String,
+    pub maker_fee_rate: Decimal,
+    pub taker_fee_rate: Decimal,
+    pub builder_fee_rate: Decimal,
 }
 
 #[derive(Deserialize, Debug)]
@@ -196,12 +241,12 @@ pub struct StarknetDomainData {
 pub struct OrderContext {
     pub asset_id_collateral: String,
     pub asset_id_synthetic: String,
-    pub settlement_resolution_collateral: String,
-    pub settlement_resolution_synthetic: String,
-    pub min_order_size_change: String,
-    pub min_price_change: String,
-    pub max_position_value: String,
-    pub fee_rate: String,
+    pub settlement_resolution_collateral: Decimal,
+    pub settlement_resolution_synthetic: Decimal,
+    pub min_order_size_change: Decimal,
+    pub min_price_change: Decimal,
+    pub max_position_value: Decimal,
+    pub fee_rate: Decimal,
     pub vault_id: String,
     pub stark_private_key: String,
     pub starknet_domain: StarknetDomainData,
@@ -210,6 +255,21 @@ pub struct OrderContext {
 #[derive(Deserialize, Debug)]
 pub struct PlaceOrderResponse {
     pub status: String,
+    pub data: Option<PlaceOrderResponseData>,
+    pub error: Option<ErrorDetail>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaceOrderResponseData {
+    pub id: u64,
+    pub external_id: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ErrorDetail {
+    pub code: i64,
+    pub message: String,
 }
 
 #[derive(Deserialize, Debug)]
@@ -225,3 +285,89 @@ pub struct TradeableBalanceData {
     pub balance: String,
     pub available_for_trade: String,
 }
+
+#[derive(Deserialize, Debug)]
+pub struct CancelOrderResponse {
+    pub status: String,
+    pub error: Option<ErrorDetail>,
+}
+
+/// Body of `POST /api/v1/user/order/massCancel`. Every order matching any of
+/// the filters is cancelled.
+#[derive(Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MassCancelRequest {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub order_ids: Vec<u64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub external_order_ids: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub markets: Vec<String>,
+    pub cancel_all: bool,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct OpenOrdersResponse {
+    pub status: String,
+    #[serde(default)]
+    pub data: Vec<OpenOrderData>,
+    pub error: Option<ErrorDetail>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenOrderData {
+    pub id: u64,
+    pub external_id: String,
+    pub market: String,
+    /// LIMIT, MARKET, CONDITIONAL or TPSL.
+    pub r#type: String,
+    pub side: String,
+    /// NEW, PARTIALLY_FILLED, UNTRIGGERED, ...
+    pub status: String,
+    pub price: Option<Decimal>,
+    pub qty: Decimal,
+    pub filled_qty: Option<Decimal>,
+    pub reduce_only: bool,
+    pub post_only: bool,
+    pub created_time: u64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct FundingHistoryResponse {
+    pub status: String,
+    #[serde(default)]
+    pub data: Vec<FundingRateData>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct FundingRateData {
+    #[serde(rename = "m")]
+    pub market: String,
+    #[serde(rename = "f")]
+    pub funding_rate: Decimal,
+    /// Time the rate was paid in epoch milliseconds.
+    #[serde(rename = "T")]
+    pub timestamp: u64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CandlesResponse {
+    pub status: String,
+    #[serde(default)]
+    pub data: Vec<CandleData>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CandleData {
+    #[serde(rename = "T")]
+    pub timestamp: u64,
+    #[serde(rename = "o")]
+    pub open: Decimal,
+    #[serde(rename = "h")]
+    pub high: Decimal,
+    #[serde(rename = "l")]
+    pub low: Decimal,
+    #[serde(rename = "c")]
+    pub close: Decimal,
+}
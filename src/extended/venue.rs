@@ -0,0 +1,299 @@
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    error::bot_error::{BotError, decimal_to_f64, f64_to_decimal, parse_number},
+    extended::{
+        account::{
+            get_open_positions::get_extended_open_positions,
+            get_tradeable_balance::get_extended_tradeable_balance,
+        },
+        markets::{
+            get_candles::get_extended_candles, get_funding_history::get_extended_funding_history,
+            get_market_data::get_extended_market_data,
+        },
+        orders::{
+            cancel_order::cancel_extended_order,
+            mass_cancel::mass_cancel_extended_orders,
+            place_limit_order::place_extended_limit_order,
+            place_order::{get_fees, prepare_extended_order, submit_extended_order},
+            place_tp_sl::place_extended_tp_sl,
+        },
+        structs::{
+            CandleData, MarketInfoData, MassCancelRequest, OpenPositionData, PlaceOrder,
+            Side as ExtendedSide, TradeableBalanceData,
+        },
+    },
+    funding::funding_interval_hours::funding_interval_hours,
+    utils::venue_client::VenueClient,
+    venue::{
+        perp_venue::PerpVenue,
+        structs::{
+            Balance, Candle, FeeRates, MarketSnapshot, OrderAck, OrderIntent, Position,
+            PositionSide, Side, TpSl,
+        },
+    },
+};
+
+/// Extended pays funding every hour unless its history shows otherwise.
+const DEFAULT_FUNDING_INTERVAL_HOURS: f64 = 1.0;
+/// How far back payments are read to measure the funding interval.
+const FUNDING_HISTORY_MILLIS: u64 = 24 * 60 * 60 * 1000;
+
+pub struct ExtendedVenue {
+    pub client: VenueClient,
+    pub api_key: String,
+    pub stark_private_key: String,
+    pub vault_id: String,
+    pub stark_public_key: String,
+}
+
+#[async_trait]
+impl PerpVenue for ExtendedVenue {
+    type MarketData = MarketInfoData;
+    type PreparedOrder = PlaceOrder;
+
+    fn name(&self) -> &str {
+        "Extended"
+    }
+
+    async fn get_market_data(&self, market_name: &str) -> anyhow::Result<MarketInfoData> {
+        // Delisted or unknown markets come back as an empty list
+        let market_data = get_extended_market_data(&self.client, market_name).await?;
+        let mut market = market_data.into_iter().next().ok_or_else(|| {
+            BotError::parse("MarketInfoData", format!("no market named {}", market_name))
+        })?;
+
+        let now_millis = Utc::now().timestamp_millis() as u64;
+        let history = get_extended_funding_history(
+            &self.client,
+            market_name,
+            now_millis.saturating_sub(FUNDING_HISTORY_MILLIS),
+            now_millis,
+        )
+        .await?;
+        market.funding_interval_hours = funding_interval_hours(
+            &history
+                .iter()
+                .map(|rate| rate.timestamp)
+                .collect::<Vec<_>>(),
+        );
+
+        Ok(market)
+    }
+
+    fn market_snapshot(&self, market: &MarketInfoData) -> anyhow::Result<MarketSnapshot> {
+        MarketSnapshot::try_from(market)
+    }
+
+    async fn get_open_positions(&self) -> anyhow::Result<Vec<Position>> {
+        get_extended_open_positions(&self.client, &self.api_key)
+            .await?
+            .iter()
+            .map(Position::try_from)
+            .collect()
+    }
+
+    async fn get_tradeable_balance(&self) -> anyhow::Result<Balance> {
+        Balance::try_from(&get_extended_tradeable_balance(&self.client, &self.api_key).await?)
+    }
+
+    async fn get_fees(&self, market_name: &str) -> anyhow::Result<FeeRates> {
+        let fees = get_fees(&self.client, market_name, &self.api_key).await?;
+        let fees = fees
+            .first()
+            .ok_or_else(|| BotError::rejected("Failed to get fees"))?;
+
+        Ok(FeeRates {
+            maker: decimal_to_f64(fees.maker_fee_rate, "maker_fee_rate")?,
+            taker: decimal_to_f64(fees.taker_fee_rate, "taker_fee_rate")?,
+        })
+    }
+
+    async fn get_candles(&self, market_name: &str, limit: usize) -> anyhow::Result<Vec<Candle>> {
+        get_extended_candles(&self.client, market_name, limit)
+            .await?
+            .iter()
+            .map(Candle::try_from)
+            .collect()
+    }
+
+    async fn prepare_order(
+        &self,
+        market_name: &str,
+        market: &MarketInfoData,
+        side: Side,
+        qty: f64,
+        slippage: f64,
+        intent: OrderIntent,
+    ) -> anyhow::Result<PlaceOrder> {
+        prepare_extended_order(
+            &self.client,
+            market_name,
+            market,
+            side.into(),
+            f64_to_decimal(qty, "qty")?,
+            f64_to_decimal(slippage, "slippage")?,
+            intent,
+            &self.api_key,
+            &self.stark_private_key,
+            &self.vault_id,
+            &self.stark_public_key,
+        )
+        .await
+    }
+
+    async fn submit_order(&self, order: PlaceOrder) -> anyhow::Result<OrderAck> {
+        submit_extended_order(&self.client, &order, &self.api_key).await
+    }
+
+    async fn place_limit_order(
+        &self,
+        market_name: &str,
+        market: &MarketInfoData,
+        side: Side,
+        qty: f64,
+        price: f64,
+        tp_sl: Option<TpSl>,
+    ) -> anyhow::Result<OrderAck> {
+        place_extended_limit_order(
+            &self.client,
+            market_name,
+            market,
+            side.into(),
+            f64_to_decimal(qty, "qty")?,
+            f64_to_decimal(price, "price")?,
+            tp_sl,
+            &self.api_key,
+            &self.stark_private_key,
+            &self.vault_id,
+            &self.stark_public_key,
+        )
+        .await
+    }
+
+    async fn place_tp_sl(
+        &self,
+        market_name: &str,
+        market: &MarketInfoData,
+        side: Side,
+        qty: f64,
+        tp_sl: TpSl,
+    ) -> anyhow::Result<()> {
+        place_extended_tp_sl(
+            &self.client,
+            market_name,
+            market,
+            side.into(),
+            f64_to_decimal(qty, "qty")?,
+            tp_sl,
+            &self.api_key,
+            &self.stark_private_key,
+            &self.vault_id,
+            &self.stark_public_key,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn cancel_order(&self, _market_name: &str, order_id: &str) -> anyhow::Result<()> {
+        cancel_extended_order(&self.client, order_id, &self.api_key).await
+    }
+
+    async fn cancel_all_orders(&self, market_name: &str) -> anyhow::Result<()> {
+        mass_cancel_extended_orders(
+            &self.client,
+            &MassCancelRequest {
+                markets: vec![market_name.to_string()],
+                ..MassCancelRequest::default()
+            },
+            &self.api_key,
+        )
+        .await
+    }
+}
+
+impl From<Side> for ExtendedSide {
+    fn from(side: Side) -> Self {
+        match side {
+            Side::Buy => ExtendedSide::Buy,
+            Side::Sell => ExtendedSide::Sell,
+        }
+    }
+}
+
+impl TryFrom<&MarketInfoData> for MarketSnapshot {
+    type Error = anyhow::Error;
+
+    fn try_from(market: &MarketInfoData) -> Result<Self, Self::Error> {
+        Ok(MarketSnapshot {
+            market: market.name.to_string(),
+            bid: decimal_to_f64(market.market_stats.bid_price, "bid_price")?,
+            ask: decimal_to_f64(market.market_stats.ask_price, "ask_price")?,
+            mark: decimal_to_f64(market.market_stats.mark_price, "mark_price")?,
+            // The projected rate is the one the next payment settles at
+            funding_rate: match market.market_stats.next_funding_rate {
+                Some(rate) => decimal_to_f64(rate, "next_funding_rate")?,
+                None => decimal_to_f64(market.market_stats.funding_rate, "funding_rate")?,
+            },
+            funding_interval_hours: market
+                .funding_interval_hours
+                .unwrap_or(DEFAULT_FUNDING_INTERVAL_HOURS),
+            max_funding_rate: market
+                .trading_config
+                .max_funding_rate
+                .map(|rate| decimal_to_f64(rate, "max_funding_rate"))
+                .transpose()?,
+            // Paid on the interval boundary, the time is not published
+            next_funding_time: None,
+            lot_size: decimal_to_f64(
+                market.trading_config.min_order_size_change,
+                "min_order_size_change",
+            )?,
+            max_order_value: None,
+            max_position_value: Some(decimal_to_f64(
+                market.trading_config.max_position_value,
+                "max_position_value",
+            )?),
+        })
+    }
+}
+
+impl TryFrom<&CandleData> for Candle {
+    type Error = anyhow::Error;
+
+    fn try_from(candle: &CandleData) -> Result<Self, Self::Error> {
+        Ok(Candle {
+            open_time: candle.timestamp,
+            high: decimal_to_f64(candle.high, "high")?,
+            low: decimal_to_f64(candle.low, "low")?,
+            close: decimal_to_f64(candle.close, "close")?,
+        })
+    }
+}
+
+impl TryFrom<&OpenPositionData> for Position {
+    type Error = anyhow::Error;
+
+    fn try_from(position: &OpenPositionData) -> Result<Self, Self::Error> {
+        Ok(Position::new(
+            position.market.to_string(),
+            position.side.parse::<PositionSide>()?,
+            parse_number(&position.size, "size")?,
+            parse_number(&position.open_price, "open_price")?,
+            position.created_at,
+        ))
+    }
+}
+
+impl TryFrom<&TradeableBalanceData> for Balance {
+    type Error = anyhow::Error;
+
+    fn try_from(balance: &TradeableBalanceData) -> Result<Self, Self::Error> {
+        Ok(Balance {
+            balance: parse_number(&balance.balance, "balance")?,
+            available_for_trade: parse_number(&balance.available_for_trade, "available_for_trade")?,
+        })
+    }
+}
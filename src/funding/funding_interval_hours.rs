@@ -0,0 +1,13 @@
+const MILLIS_IN_HOUR: f64 = 60.0 * 60.0 * 1_000.0;
+
+/// Shortest gap in hours between consecutive payment times, given oldest
+/// first in epoch milliseconds. `None` when there are not enough payments to
+/// tell.
+pub fn funding_interval_hours(payment_times: &[u64]) -> Option<f64> {
+    payment_times
+        .windows(2)
+        .map(|pair| pair[1].saturating_sub(pair[0]))
+        .filter(|gap| *gap > 0)
+        .min()
+        .map(|gap| gap as f64 / MILLIS_IN_HOUR)
+}
@@ -0,0 +1,3 @@
+pub mod funding_interval_hours;
+pub mod normalize_funding;
+pub mod structs;
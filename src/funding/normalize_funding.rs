@@ -0,0 +1,30 @@
+use crate::{funding::structs::NormalizedFunding, venue::structs::MarketSnapshot};
+
+const HOURS_PER_YEAR: f64 = 365.0 * 24.0;
+const MILLIS_IN_HOUR: u64 = 60 * 60 * 1_000;
+
+/// Converts the funding rate of a snapshot, paid once every
+/// `funding_interval_hours`, into its hourly rate and APR. Rates past the
+/// venue's cap are clamped to it, since that is all that gets paid.
+pub fn normalize_funding(snapshot: &MarketSnapshot, now_millis: u64) -> NormalizedFunding {
+    let rate = match snapshot.max_funding_rate {
+        Some(cap) => snapshot.funding_rate.clamp(-cap, cap),
+        None => snapshot.funding_rate,
+    };
+    let hourly_rate = rate / snapshot.funding_interval_hours;
+
+    NormalizedFunding {
+        hourly_rate,
+        apr: hourly_rate * HOURS_PER_YEAR * 100.0,
+        next_payment: snapshot
+            .next_funding_time
+            .unwrap_or_else(|| next_interval_boundary(now_millis, snapshot.funding_interval_hours)),
+    }
+}
+
+/// Start of the next funding interval, for venues that pay on fixed UTC
+/// boundaries without publishing the time.
+fn next_interval_boundary(now_millis: u64, funding_interval_hours: f64) -> u64 {
+    let interval = ((funding_interval_hours * MILLIS_IN_HOUR as f64) as u64).max(1);
+    (now_millis / interval + 1) * interval
+}
@@ -0,0 +1,31 @@
+use std::fmt;
+
+use chrono::DateTime;
+
+/// Funding rate of one market on a common basis, so venues that pay funding
+/// at different intervals can be compared.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NormalizedFunding {
+    /// Rate paid per hour as a fraction, positive when longs pay shorts.
+    pub hourly_rate: f64,
+    /// The hourly rate over a year, in percent.
+    pub apr: f64,
+    /// When the rate is next paid, in epoch milliseconds.
+    pub next_payment: u64,
+}
+
+impl fmt::Display for NormalizedFunding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let next_payment = DateTime::from_timestamp_millis(self.next_payment as i64)
+            .map(|time| time.to_rfc3339())
+            .unwrap_or_else(|| self.next_payment.to_string());
+
+        write!(
+            f,
+            "{}%/h, APR {}%, next payment {}",
+            self.hourly_rate * 100.0,
+            self.apr,
+            next_payment
+        )
+    }
+}
@@ -0,0 +1,11 @@
+pub mod config;
+pub mod discovery;
+pub mod error;
+pub mod extended;
+pub mod funding;
+pub mod pacifica;
+pub mod portfolio;
+pub mod sizing;
+pub mod strategy;
+pub mod utils;
+pub mod venue;
@@ -1,40 +1,36 @@
+use anyhow::anyhow;
 use chrono::{FixedOffset, Timelike, Utc};
 use dotenvy::dotenv;
 use tokio::time::Duration;
 
-use crate::{
-    extended::{
-        account::{
-            get_open_positions::get_extended_open_positions,
-            get_tradeable_balance::get_extended_tradeable_balance,
-        },
-        markets::get_market_data::get_extended_market_data,
-        orders::place_order::place_extended_order,
-        structs::{OpenPositionData as ExtendedOpenPositionData, Side as ExtendedSide},
+use funding_rate_bot::{
+    config::{load_config::load_config, structs::Config},
+    discovery::discover_pairs::{apply_discovery, contract_ratio},
+    error::bot_error::BotError,
+    extended::venue::ExtendedVenue,
+    pacifica::venue::PacificaVenue,
+    portfolio::load_portfolio::load_portfolio,
+    strategy::{
+        close_if_necessary::close_if_necessary,
+        handle_orphan::handle_orphan,
+        place_arb_order::{evaluate_pair, open_pair},
+        plan_entries::plan_entries,
+        repair_imbalance::repair_imbalance,
+        structs::VenueLeg,
     },
-    pacifica::{
-        account::{
-            get_open_positions::get_pacifica_open_positions,
-            get_tradeable_balance::get_pacifica_tradeable_balance,
-        },
-        markets::get_market_data::get_pacifica_market_data,
-        orders::place_order::place_pacifica_order,
-        structs::{OpenPositionData as PacificaOpenPositionData, Side as PacificaSide},
+    utils::venue_client::VenueClient,
+    venue::{
+        paper_venue::PaperVenue,
+        perp_venue::PerpVenue,
+        structs::{FeeRates, Position},
     },
 };
 
-mod extended;
-mod pacifica;
-mod utils;
-
-const FUNDING_RATE_THRESHOLD: f64 = 0.001;
-const PRICE_SPREAD_THRESHOLD: f64 = 0.02;
+const READ_ATTEMPTS: u32 = 3;
+const READ_RETRY_DELAY_SECONDS: u64 = 2;
 
-const TARGET_MINUTE: u32 = 28;
-const BUY_AMOUNT: f64 = 25.0;
-
-/// Calculates the duration until the next target minute (:29) in IST
-fn duration_until_next_target() -> Duration {
+/// Calculates the duration until the next target minute in IST
+fn duration_until_next_target(target_minute: u32) -> Duration {
     // IST is UTC+5:30
     let ist = FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap();
     let now_ist = Utc::now().with_timezone(&ist);
@@ -42,17 +38,17 @@ fn duration_until_next_target() -> Duration {
     let current_minute = now_ist.minute();
     let current_second = now_ist.second();
 
-    let minutes_until_target = if current_minute < TARGET_MINUTE {
-        TARGET_MINUTE - current_minute
+    let minutes_until_target = if current_minute < target_minute {
+        target_minute - current_minute
     } else {
-        // Next hour's :29
-        60 - current_minute + TARGET_MINUTE
+        // Next hour's target minute
+        60 - current_minute + target_minute
     };
 
     // Calculate total seconds, subtracting current seconds within the minute
     let total_seconds = (minutes_until_target * 60) as i64 - current_second as i64;
 
-    // If we're exactly at :29:00, wait for next hour
+    // If we're exactly at the target minute, wait for next hour
     let total_seconds = if total_seconds <= 0 {
         total_seconds + 3600
     } else {
@@ -65,62 +61,101 @@ fn duration_until_next_target() -> Duration {
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     dotenv().ok();
-    let extended_market_names = vec![
-        String::from("ETH-USD"),
-        String::from("HYPE-USD"),
-        String::from("1000BONK-USD"),
-        String::from("1000PEPE-USD"),
-        String::from("PENGU-USD"),
-        String::from("DOGE-USD"),
-        String::from("UNI-USD"),
-        String::from("SOL-USD"),
-        String::from("PUMP-USD"),
-        String::from("XRP-USD"),
-        String::from("ASTER-USD"),
-        String::from("AVAX-USD"),
-        String::from("TRUMP-USD"),
-        String::from("SUI-USD"),
-        String::from("FARTCOIN-USD"),
-        String::from("LINK-USD"),
-    ];
-
-    let pacifica_market_names = vec![
-        String::from("ETH"),
-        String::from("HYPE"),
-        String::from("kBONK"),
-        String::from("kPEPE"),
-        String::from("PENGU"),
-        String::from("DOGE"),
-        String::from("UNI"),
-        String::from("SOL"),
-        String::from("PUMP"),
-        String::from("XRP"),
-        String::from("ASTER"),
-        String::from("AVAX"),
-        String::from("TRUMP"),
-        String::from("SUI"),
-        String::from("FARTCOIN"),
-        String::from("LINK"),
-    ];
-
-    let pacifica_wallet_address =
-        std::env::var("PACIFICA_WALLET_ADDRESS").expect("PACIFICA_WALLET_ADDRESS must be set");
-    let pacifica_private_key =
-        std::env::var("PACIFICA_PRIVATE_KEY").expect("PACIFICA_PRIVATE_KEY must be set");
-    let extended_api_key = std::env::var("EXTENDED_API_KEY").expect("EXTENDED_API_KEY must be set");
-    let extended_stark_private_key = std::env::var("EXTENDED_STARK_PRIVATE_KEY")
-        .expect("EXTENDED_STARK_PRIVATE_KEY must be set");
-    let extended_vault_id =
-        std::env::var("EXTENDED_VAULT_ID").expect("EXTENDED_VAULT_ID must be set");
-    let extended_stark_public_key =
-        std::env::var("EXTENDED_STARK_PUBLIC_KEY").expect("EXTENDED_STARK_PUBLIC_KEY must be set");
+    let config_path = std::env::var("CONFIG_PATH").unwrap_or(String::from("config.toml"));
+    let config = load_config(&config_path)?;
+    println!(
+        "Loaded {} market pairs from {}",
+        config.pairs.len(),
+        config_path
+    );
+
+    // Orders are never signed in a dry run, so the credentials are optional
+    let dry_run = config.dry_run.enabled;
+    let extended = ExtendedVenue {
+        client: VenueClient::new(&config.extended)?,
+        api_key: env_var("EXTENDED_API_KEY", dry_run),
+        stark_private_key: env_var("EXTENDED_STARK_PRIVATE_KEY", dry_run),
+        vault_id: env_var("EXTENDED_VAULT_ID", dry_run),
+        stark_public_key: env_var("EXTENDED_STARK_PUBLIC_KEY", dry_run),
+    };
+    let pacifica = PacificaVenue {
+        client: VenueClient::new(&config.pacifica)?,
+        private_key: env_var("PACIFICA_PRIVATE_KEY", dry_run),
+        wallet_address: env_var("PACIFICA_WALLET_ADDRESS", dry_run),
+        fees: FeeRates {
+            maker: config.fees.pacifica_maker_fee,
+            taker: config.fees.pacifica_taker_fee,
+        },
+    };
+
+    let extended_client = extended.client.clone();
+    let pacifica_client = pacifica.client.clone();
+
+    if dry_run {
+        println!("Dry run enabled, orders are simulated and never sent");
+        let extended = PaperVenue::new(
+            extended,
+            config.dry_run.extended_balance,
+            config.dry_run.leverage,
+            FeeRates {
+                maker: config.dry_run.extended_maker_fee,
+                taker: config.dry_run.extended_taker_fee,
+            },
+        );
+        let pacifica = PaperVenue::new(
+            pacifica,
+            config.dry_run.pacifica_balance,
+            config.dry_run.leverage,
+            FeeRates {
+                maker: config.dry_run.pacifica_maker_fee,
+                taker: config.dry_run.pacifica_taker_fee,
+            },
+        );
+        run(
+            config,
+            &extended_client,
+            &pacifica_client,
+            &extended,
+            &pacifica,
+        )
+        .await
+    } else {
+        run(
+            config,
+            &extended_client,
+            &pacifica_client,
+            &extended,
+            &pacifica,
+        )
+        .await
+    }
+}
+
+fn env_var(name: &str, optional: bool) -> String {
+    match std::env::var(name) {
+        Ok(value) => value,
+        Err(_) if optional => String::new(),
+        Err(_) => panic!("{} must be set", name),
+    }
+}
+
+/// Runs the hourly cycle forever: repair hedges that drifted apart, close the
+/// pairs the exit rules pick, then rank the new entries and open the best ones.
+async fn run<E: PerpVenue, P: PerpVenue>(
+    mut config: Config,
+    extended_client: &VenueClient,
+    pacifica_client: &VenueClient,
+    extended: &E,
+    pacifica: &P,
+) -> anyhow::Result<()> {
+    let target_minute = config.target_minute;
 
     loop {
-        let wait_duration = duration_until_next_target();
+        let wait_duration = duration_until_next_target(target_minute);
         let ist = FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap();
         let now_ist = Utc::now().with_timezone(&ist);
         println!(
-            "Current Time IST: {}, Waiting for {} seconds until next :{TARGET_MINUTE} IST",
+            "Current Time IST: {}, Waiting for {} seconds until next :{target_minute} IST",
             now_ist.format("%H:%M:%S"),
             wait_duration.as_secs()
         );
@@ -133,319 +168,252 @@ async fn main() -> anyhow::Result<()> {
             now_ist.format("%H:%M:%S")
         );
 
-        let extended_open_positions = get_extended_open_positions(&extended_api_key).await?;
-        println!("Extended Open Positions: {:?}", extended_open_positions);
+        if config.discovery.enabled
+            && let Err(e) = apply_discovery(&mut config, extended_client, pacifica_client).await
+        {
+            println!("Failed to discover market pairs: {}", e);
+        }
+
+        let Some((mut extended_open_positions, mut pacifica_open_positions)) =
+            fetch_open_positions(extended, pacifica).await
+        else {
+            continue;
+        };
+
+        if config.rebalance.enabled {
+            let mut repaired = false;
+            for pair in config.pairs.iter() {
+                let result = repair_imbalance(
+                    VenueLeg {
+                        venue: extended,
+                        leg: &pair.extended,
+                    },
+                    extended_open_positions
+                        .iter()
+                        .find(|p| p.market == pair.extended.market),
+                    VenueLeg {
+                        venue: pacifica,
+                        leg: &pair.pacifica,
+                    },
+                    pacifica_open_positions
+                        .iter()
+                        .find(|p| p.market == pair.pacifica.market),
+                    contract_ratio(&pair.extended.market, &pair.pacifica.market),
+                    &config.rebalance,
+                )
+                .await;
+
+                match result {
+                    Ok(sent) => repaired |= sent,
+                    Err(e) => report_result(&pair.extended.market, Err(e)),
+                }
+            }
 
-        let pacifica_open_positions = get_pacifica_open_positions(&pacifica_wallet_address).await?;
-        println!("Pacific Open Positions: {:?}", pacifica_open_positions);
+            // The close and entry checks below must see the repaired positions
+            if repaired {
+                let Some(positions) = fetch_open_positions(extended, pacifica).await else {
+                    continue;
+                };
+                (extended_open_positions, pacifica_open_positions) = positions;
+            }
+        }
 
-        for i in 0..extended_open_positions.len() {
-            let pacifica_market_index = extended_market_names
+        for extended_open_position in extended_open_positions.iter() {
+            let market = &extended_open_position.market;
+            let Some(pair) = config.pairs.iter().find(|p| &p.extended.market == market) else {
+                report_result(market, Err(anyhow!("Extended position in unmapped market")));
+                continue;
+            };
+
+            let result = match pacifica_open_positions
                 .iter()
-                .position(|p| *p == extended_open_positions[i].market)
-                .unwrap();
-            let result = close_if_necessary(
-                &extended_open_positions[i].market,
-                &pacifica_market_names[pacifica_market_index],
-                &extended_open_positions[i],
-                &pacifica_open_positions
-                    .iter()
-                    .find(|p| p.symbol == pacifica_market_names[pacifica_market_index])
-                    .unwrap(),
-                &extended_api_key,
-                &extended_stark_private_key,
-                &extended_vault_id,
-                &extended_stark_public_key,
-                &pacifica_private_key,
-                &pacifica_wallet_address,
-            )
-            .await;
+                .find(|p| p.market == pair.pacifica.market)
+            {
+                Some(pacifica_open_position) => close_if_necessary(
+                    extended,
+                    &pair.extended,
+                    extended_open_position,
+                    pacifica,
+                    &pair.pacifica,
+                    pacifica_open_position,
+                    &pair.exit,
+                )
+                .await
+                .map(|_| ()),
+                None => {
+                    handle_orphan(
+                        VenueLeg {
+                            venue: extended,
+                            leg: &pair.extended,
+                        },
+                        extended_open_position,
+                        VenueLeg {
+                            venue: pacifica,
+                            leg: &pair.pacifica,
+                        },
+                        1.0 / contract_ratio(&pair.extended.market, &pair.pacifica.market),
+                        pair.orphan_policy,
+                        &pair.tp_sl,
+                    )
+                    .await
+                }
+            };
+
+            report_result(market, result);
+        }
+
+        // Pacifica positions with an Extended counterpart were handled above
+        for pacifica_open_position in pacifica_open_positions.iter() {
+            let market = &pacifica_open_position.market;
+            let Some(pair) = config.pairs.iter().find(|p| &p.pacifica.market == market) else {
+                report_result(market, Err(anyhow!("Pacifica position in unmapped market")));
+                continue;
+            };
 
-            match result {
-                Ok(_) => println!(
-                    "-------------------------------- Success for market {} --------------------------------",
-                    extended_open_positions[i].market
-                ),
-                Err(e) => println!(
-                    "-------------------------------- Error for market {} : {} --------------------------------",
-                    extended_open_positions[i].market, e
-                ),
+            let is_hedged = extended_open_positions
+                .iter()
+                .any(|p| p.market == pair.extended.market);
+            if is_hedged {
+                continue;
             }
-        }
 
-        for i in 0..extended_market_names.len() {
-            let result = place_arb_order(
-                &extended_market_names[i],
-                &pacifica_market_names[i],
-                &extended_api_key,
-                &extended_stark_private_key,
-                &extended_vault_id,
-                &extended_stark_public_key,
-                &pacifica_private_key,
-                &pacifica_wallet_address,
+            let result = handle_orphan(
+                VenueLeg {
+                    venue: pacifica,
+                    leg: &pair.pacifica,
+                },
+                pacifica_open_position,
+                VenueLeg {
+                    venue: extended,
+                    leg: &pair.extended,
+                },
+                contract_ratio(&pair.extended.market, &pair.pacifica.market),
+                pair.orphan_policy,
+                &pair.tp_sl,
             )
             .await;
 
-            match result {
-                Ok(_) => println!(
-                    "-------------------------------- Success for market {} --------------------------------",
-                    extended_market_names[i]
-                ),
-                Err(e) => println!(
-                    "-------------------------------- Error for market {} : {} --------------------------------",
-                    extended_market_names[i], e
-                ),
-            }
+            report_result(market, result);
         }
-    }
-}
 
-async fn close_if_necessary(
-    extended_market_name: &str,
-    pacifica_market_name: &str,
-    extended_open_position: &ExtendedOpenPositionData,
-    pacifica_open_position: &PacificaOpenPositionData,
-    extended_api_key: &str,
-    extended_stark_private_key: &str,
-    extended_vault_id: &str,
-    extended_stark_public_key: &str,
-    pacifica_private_key: &str,
-    pacifica_wallet_address: &str,
-) -> anyhow::Result<()> {
-    println!(
-        "Closing if necessary for market: {} and {}",
-        extended_market_name, pacifica_market_name
-    );
-    let extended_result_vec = get_extended_market_data(extended_market_name).await?;
-    let extended_result = extended_result_vec.first().unwrap();
-    let pacifica_result = get_pacifica_market_data(pacifica_market_name).await?;
-
-    let funding_rate_extended = extended_result.market_stats.funding_rate.parse::<f64>()? * 100.0;
-    let funding_rate_pacifica = pacifica_result.next_funding.parse::<f64>()? * 100.0;
-
-    if funding_rate_extended > funding_rate_pacifica {
-        if extended_open_position.side == "LONG" {
-            // Close long
-            place_extended_order(
-                &extended_market_name,
-                &extended_result,
-                ExtendedSide::Sell,
-                extended_open_position.size.parse::<f64>()?,
-                false,
-                &extended_api_key,
-                &extended_stark_private_key,
-                &extended_vault_id,
-                &extended_stark_public_key,
-            )
-            .await?;
-        }
+        // The entries below must see the positions left after the closes
+        let portfolio = match with_retries(|| load_portfolio(extended, pacifica)).await {
+            Ok(portfolio) => portfolio,
+            Err(e) => {
+                report_result("Portfolio", Err(e));
+                continue;
+            }
+        };
 
-        if pacifica_open_position.side == "SHORT" {
-            // Close short
-            place_pacifica_order(
-                pacifica_market_name,
-                PacificaSide::Bid,
-                pacifica_open_position.amount.parse::<f64>()?,
-                &pacifica_result,
-                false,
-                &pacifica_private_key,
-                &pacifica_wallet_address,
-            )
-            .await?;
-        }
-    } else {
-        if extended_open_position.side == "SHORT" {
-            // Close short
-            place_extended_order(
-                &extended_market_name,
-                &extended_result,
-                ExtendedSide::Buy,
-                extended_open_position.size.parse::<f64>()?,
-                false,
-                &extended_api_key,
-                &extended_stark_private_key,
-                &extended_vault_id,
-                &extended_stark_public_key,
-            )
-            .await?;
+        let mut opportunities = Vec::new();
+        for pair in config.pairs.iter() {
+            // Pairs already held are left to the exit rules instead of being added to
+            if portfolio.holds(&pair.extended, &pair.pacifica) {
+                continue;
+            }
+
+            match evaluate_pair(extended, &pair.extended, pacifica, &pair.pacifica, pair).await {
+                Ok(opportunity) => opportunities.push((pair, opportunity)),
+                Err(e) => report_result(&pair.extended.market, Err(e)),
+            }
         }
 
-        if pacifica_open_position.side == "LONG" {
-            // Close long
-            place_pacifica_order(
-                pacifica_market_name,
-                PacificaSide::Ask,
-                pacifica_open_position.amount.parse::<f64>()?,
-                &pacifica_result,
-                false,
-                &pacifica_private_key,
-                &pacifica_wallet_address,
+        let planned = plan_entries(opportunities, &portfolio, &config.allocation);
+
+        for entry in planned {
+            let pair = entry.pair;
+            let result = open_pair(
+                extended,
+                &pair.extended,
+                pacifica,
+                &pair.pacifica,
+                pair,
+                &entry.opportunity,
+                entry.size,
             )
-            .await?;
+            .await;
+
+            report_result(&pair.extended.market, result);
         }
     }
-
-    Ok(())
 }
 
-async fn place_arb_order(
-    extended_market_name: &str,
-    pacifica_market_name: &str,
-    extended_api_key: &str,
-    extended_stark_private_key: &str,
-    extended_vault_id: &str,
-    extended_stark_public_key: &str,
-    pacifica_private_key: &str,
-    pacifica_wallet_address: &str,
-) -> anyhow::Result<()> {
-    println!(
-        "Checking funding arb for market: {} and {}",
-        extended_market_name, pacifica_market_name
-    );
-    let extended_result_vec = get_extended_market_data(extended_market_name).await?;
-    let extended_result = extended_result_vec.first().unwrap();
-    let pacifica_result = get_pacifica_market_data(pacifica_market_name).await?;
-
-    let funding_rate_extended = extended_result.market_stats.funding_rate.parse::<f64>()? * 100.0;
-    let funding_rate_pacifica = pacifica_result.next_funding.parse::<f64>()? * 100.0;
-
-    let price_extended = extended_result.market_stats.bid_price.parse::<f64>()?;
-    let price_pacifica = pacifica_result.mid.parse::<f64>()?;
-
-    let price_spread = if price_extended > price_pacifica {
-        let price_diff = price_extended - price_pacifica;
-        price_diff / price_extended * 100.0
-    } else {
-        let price_diff = price_pacifica - price_extended;
-        price_diff / price_pacifica * 100.0
+/// Reads the open positions on both venues, reporting and returning `None`
+/// when either venue cannot be read.
+async fn fetch_open_positions<E: PerpVenue, P: PerpVenue>(
+    extended: &E,
+    pacifica: &P,
+) -> Option<(Vec<Position>, Vec<Position>)> {
+    let extended_open_positions = match with_retries(|| extended.get_open_positions()).await {
+        Ok(positions) => positions,
+        Err(e) => {
+            report_result("Extended open positions", Err(e));
+            return None;
+        }
     };
+    println!("Extended Open Positions: {:?}", extended_open_positions);
 
-    let funding_rate_diff = (funding_rate_extended - funding_rate_pacifica).abs();
-
-    println!("Extended Funding Rate: {}", funding_rate_extended);
-    println!("Pacific Funding Rate: {}", funding_rate_pacifica);
-    println!("Price Spread: {}", price_spread);
-    println!("Funding Rate Diff: {}", funding_rate_diff);
-
-    let extended_tradeable_balance = get_extended_tradeable_balance(extended_api_key)
-        .await?
-        .available_for_trade
-        .parse::<f64>()?;
-    let pacifica_tradeable_balance = get_pacifica_tradeable_balance(pacifica_wallet_address)
-        .await?
-        .available_to_spend
-        .parse::<f64>()?;
-
-    let min_amount = (if extended_result.market_stats.bid_price.parse::<f64>()?
-        < pacifica_result.mid.parse::<f64>()?
-    {
-        extended_result.market_stats.bid_price.parse::<f64>()?
-    } else {
-        pacifica_result.mid.parse::<f64>()?
-    }) * 0.99;
+    let pacifica_open_positions = match with_retries(|| pacifica.get_open_positions()).await {
+        Ok(positions) => positions,
+        Err(e) => {
+            report_result("Pacifica open positions", Err(e));
+            return None;
+        }
+    };
+    println!("Pacific Open Positions: {:?}", pacifica_open_positions);
 
-    let tradeable_amount = BUY_AMOUNT / min_amount;
+    Some((extended_open_positions, pacifica_open_positions))
+}
 
-    if funding_rate_extended > funding_rate_pacifica {
-        // SHORT on extended, LONG on pacifica
-        if price_spread > PRICE_SPREAD_THRESHOLD || funding_rate_diff < FUNDING_RATE_THRESHOLD {
-            return Err(anyhow::anyhow!(
-                "Price Spread or Funding Rate Diff is too high"
-            ));
+/// Retries a read-only request while it fails with a retryable error.
+async fn with_retries<T, F, Fut>(request: F) -> anyhow::Result<T>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = anyhow::Result<T>>,
+{
+    let mut attempt = 1;
+    loop {
+        match request().await {
+            Err(e) if attempt < READ_ATTEMPTS && is_retryable(&e) => {
+                println!("Attempt {} failed, retrying: {}", attempt, e);
+                tokio::time::sleep(Duration::from_secs(READ_RETRY_DELAY_SECONDS)).await;
+                attempt += 1;
+            }
+            result => return result,
         }
+    }
+}
 
-        if extended_tradeable_balance < BUY_AMOUNT || pacifica_tradeable_balance < BUY_AMOUNT {
-            return Err(anyhow::anyhow!("Tradeable balance is too low"));
-        }
+fn is_retryable(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<BotError>()
+        .is_some_and(|e| e.is_retryable())
+}
 
-        place_extended_order(
-            &extended_market_name,
-            &extended_result,
-            ExtendedSide::Sell,
-            tradeable_amount,
-            true,
-            &extended_api_key,
-            &extended_stark_private_key,
-            &extended_vault_id,
-            &extended_stark_public_key,
-        )
-        .await?;
-        let has_placed = place_pacifica_order(
-            pacifica_market_name,
-            PacificaSide::Bid,
-            tradeable_amount,
-            &pacifica_result,
-            true,
-            &pacifica_private_key,
-            &pacifica_wallet_address,
-        )
-        .await;
-
-        if has_placed.is_err() {
-            place_extended_order(
-                &extended_market_name,
-                &extended_result,
-                ExtendedSide::Buy,
-                tradeable_amount,
-                false,
-                &extended_api_key,
-                &extended_stark_private_key,
-                &extended_vault_id,
-                &extended_stark_public_key,
-            )
-            .await?;
-            return Err(anyhow::anyhow!("Failed to place pacifica order"));
-        }
-    } else {
-        if price_spread > PRICE_SPREAD_THRESHOLD || funding_rate_diff < FUNDING_RATE_THRESHOLD {
-            return Err(anyhow::anyhow!(
-                "Price Spread or Funding Rate Diff is too high"
-            ));
-        }
+/// Logs the outcome of handling a market according to the kind of error:
+/// skips are routine, network failures are retried next cycle and anything
+/// else is raised as an alert.
+fn report_result(market: &str, result: anyhow::Result<()>) {
+    let Err(e) = result else {
+        println!(
+            "-------------------------------- Success for market {} --------------------------------",
+            market
+        );
+        return;
+    };
 
-        if extended_tradeable_balance < BUY_AMOUNT || pacifica_tradeable_balance < BUY_AMOUNT {
-            return Err(anyhow::anyhow!("Tradeable balance is too low"));
-        }
-        // LONG on extended, SHORT on pacifica
-        place_extended_order(
-            &extended_market_name,
-            &extended_result,
-            ExtendedSide::Buy,
-            tradeable_amount,
-            true,
-            &extended_api_key,
-            &extended_stark_private_key,
-            &extended_vault_id,
-            &extended_stark_public_key,
-        )
-        .await?;
-        let has_placed = place_pacifica_order(
-            pacifica_market_name,
-            PacificaSide::Ask,
-            tradeable_amount,
-            &pacifica_result,
-            true,
-            &pacifica_private_key,
-            &pacifica_wallet_address,
-        )
-        .await;
-
-        if has_placed.is_err() {
-            place_extended_order(
-                &extended_market_name,
-                &extended_result,
-                ExtendedSide::Sell,
-                tradeable_amount,
-                false,
-                &extended_api_key,
-                &extended_stark_private_key,
-                &extended_vault_id,
-                &extended_stark_public_key,
-            )
-            .await?;
-            return Err(anyhow::anyhow!("Failed to place extended order"));
-        }
+    match e.downcast_ref::<BotError>() {
+        Some(BotError::Skip(reason)) => println!(
+            "-------------------------------- Skipped market {} : {} --------------------------------",
+            market, reason
+        ),
+        Some(bot_error) if bot_error.is_retryable() => println!(
+            "-------------------------------- Error for market {} (retrying next cycle) : {:#} --------------------------------",
+            market, e
+        ),
+        _ => eprintln!(
+            "-------------------------------- ALERT for market {} : {:#} --------------------------------",
+            market, e
+        ),
     }
-
-    Ok(())
 }
@@ -1,23 +1,21 @@
-use crate::pacifica::structs::{OpenPosition, OpenPositionData};
+use crate::{
+    error::bot_error::BotError,
+    pacifica::structs::{OpenPosition, OpenPositionData},
+    utils::venue_client::VenueClient,
+};
 
 pub async fn get_pacifica_open_positions(
+    client: &VenueClient,
     wallet_address: &str,
 ) -> anyhow::Result<Vec<OpenPositionData>> {
-    let url = format!(
-        "https://api.pacifica.fi/api/v1/positions?account={}",
-        wallet_address
-    );
+    let url = client.url(&format!("/api/v1/positions?account={}", wallet_address));
 
-    let client = reqwest::Client::new();
     let open_orders_data = client
-        .get(&url)
-        .send()
-        .await?
-        .json::<OpenPosition>()
+        .send_json::<OpenPosition>(client.http.get(&url))
         .await?;
 
     if open_orders_data.success == false {
-        return Err(anyhow::anyhow!("Failed to get open orders"));
+        return Err(BotError::rejected("Failed to get open orders").into());
     }
 
     Ok(open_orders_data.data)
@@ -1,23 +1,21 @@
-use crate::pacifica::structs::{TradeableBalance, TradeableBalanceData};
+use crate::{
+    error::bot_error::BotError,
+    pacifica::structs::{TradeableBalance, TradeableBalanceData},
+    utils::venue_client::VenueClient,
+};
 
 pub async fn get_pacifica_tradeable_balance(
+    client: &VenueClient,
     wallet_address: &str,
 ) -> anyhow::Result<TradeableBalanceData> {
-    let url = format!(
-        "https://api.pacifica.fi/api/v1/account?account={}",
-        wallet_address
-    );
+    let url = client.url(&format!("/api/v1/account?account={}", wallet_address));
 
-    let client = reqwest::Client::new();
     let tradeable_balance_data = client
-        .get(&url)
-        .send()
-        .await?
-        .json::<TradeableBalance>()
+        .send_json::<TradeableBalance>(client.http.get(&url))
         .await?;
 
     if tradeable_balance_data.success == false {
-        return Err(anyhow::anyhow!("Failed to get tradeable balance"));
+        return Err(BotError::rejected("Failed to get tradeable balance").into());
     }
 
     Ok(tradeable_balance_data.data)
@@ -0,0 +1,33 @@
+use chrono::Utc;
+
+use crate::{
+    error::bot_error::BotError,
+    pacifica::structs::{KlineData, Klines},
+    utils::venue_client::VenueClient,
+};
+
+const MILLIS_IN_HOUR: i64 = 60 * 60 * 1_000;
+
+/// Hourly candles of the market covering the last `limit` hours, oldest first.
+pub async fn get_pacifica_candles(
+    client: &VenueClient,
+    market_name: &str,
+    limit: usize,
+) -> anyhow::Result<Vec<KlineData>> {
+    let start_time = Utc::now().timestamp_millis() - limit as i64 * MILLIS_IN_HOUR;
+    let url = client.url(&format!(
+        "/api/v1/kline?symbol={}&interval=1h&start_time={}",
+        market_name, start_time
+    ));
+
+    let klines = client.send_json::<Klines>(client.http.get(&url)).await?;
+
+    if klines.success == false {
+        return Err(BotError::rejected("Invalid Candle Data").into());
+    }
+
+    let mut klines = klines.data;
+    klines.sort_by_key(|kline| kline.t);
+
+    Ok(klines)
+}
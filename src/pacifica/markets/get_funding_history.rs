@@ -0,0 +1,30 @@
+use crate::{
+    error::bot_error::BotError,
+    pacifica::structs::{FundingHistoryResponse, FundingRateData},
+    utils::venue_client::VenueClient,
+};
+
+/// Latest `limit` funding rates the market paid, oldest first.
+pub async fn get_pacifica_funding_history(
+    client: &VenueClient,
+    market_name: &str,
+    limit: u32,
+) -> anyhow::Result<Vec<FundingRateData>> {
+    let url = client.url(&format!(
+        "/api/v1/funding_rate/history?symbol={}&limit={}",
+        market_name, limit
+    ));
+
+    let history = client
+        .send_json::<FundingHistoryResponse>(client.http.get(&url))
+        .await?;
+
+    if !history.success {
+        return Err(BotError::rejected("Invalid Funding History").into());
+    }
+
+    let mut rates = history.data;
+    rates.sort_by_key(|rate| rate.created_at);
+
+    Ok(rates)
+}
@@ -1,32 +1,27 @@
-use anyhow::anyhow;
+use crate::{
+    error::bot_error::BotError,
+    pacifica::structs::{MarketInfoData, MarketPricesInfo, MarketTradingInfo},
+    utils::venue_client::VenueClient,
+};
 
-use crate::pacifica::structs::{MarketInfoData, MarketPricesInfo, MarketTradingInfo};
-
-pub async fn get_pacifica_market_data(market_name: &str) -> anyhow::Result<MarketInfoData> {
-    // Create a client with browser-like headers
-    let client = reqwest::Client::new();
+pub async fn get_pacifica_market_data(
+    client: &VenueClient,
+    market_name: &str,
+) -> anyhow::Result<MarketInfoData> {
     let market_price_data = client
-        .get("https://api.pacifica.fi/api/v1/info/prices")
-        .header("User-Agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-        .send()
-        .await?
-        .json::<MarketPricesInfo>()
+        .send_json::<MarketPricesInfo>(client.http.get(client.url("/api/v1/info/prices")))
         .await?;
 
-    let market_trading_data= client
-    .get("https://api.pacifica.fi/api/v1/info")
-    .header("User-Agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-    .send()
-    .await?
-    .json::<MarketTradingInfo>()
-    .await?;
+    let market_trading_data = client
+        .send_json::<MarketTradingInfo>(client.http.get(client.url("/api/v1/info")))
+        .await?;
 
     if market_price_data.success == false || market_price_data.data.len() == 0 {
-        return Err(anyhow!("Invalid Market Data"));
+        return Err(BotError::rejected("Invalid Market Data").into());
     }
 
     if market_trading_data.success == false || market_trading_data.data.len() == 0 {
-        return Err(anyhow!("Invalid Market Data"));
+        return Err(BotError::rejected("Invalid Market Data").into());
     }
 
     for data in market_price_data.data {
@@ -35,19 +30,22 @@ pub async fn get_pacifica_market_data(market_name: &str) -> anyhow::Result<Marke
                 if trading_data.symbol == market_name {
                     return Ok(MarketInfoData {
                         mid: data.mid,
+                        mark: data.mark,
                         next_funding: data.next_funding,
                         symbol: data.symbol,
-                        tick_size: trading_data.tick_size.to_string(),
-                        min_tick: trading_data.min_tick.to_string(),
-                        max_tick: trading_data.max_tick.to_string(),
-                        lot_size: trading_data.lot_size.to_string(),
-                        min_order_size: trading_data.min_order_size.to_string(),
-                        max_order_size: trading_data.max_order_size.to_string(),
+                        tick_size: trading_data.tick_size,
+                        min_tick: trading_data.min_tick,
+                        max_tick: trading_data.max_tick,
+                        lot_size: trading_data.lot_size,
+                        min_order_size: trading_data.min_order_size,
+                        max_order_size: trading_data.max_order_size,
+                        max_funding_rate: trading_data.max_funding_rate,
+                        funding_interval_hours: None,
                     });
                 }
             }
         }
     }
 
-    Err(anyhow!("Market Data not found"))
+    Err(BotError::rejected("Market Data not found").into())
 }
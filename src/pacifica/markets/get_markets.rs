@@ -0,0 +1,20 @@
+use crate::{
+    error::bot_error::BotError,
+    pacifica::structs::{MarketTradingInfo, MarketTradingInfoData},
+    utils::venue_client::VenueClient,
+};
+
+/// Lists every market available on Pacifica.
+pub async fn get_pacifica_markets(
+    client: &VenueClient,
+) -> anyhow::Result<Vec<MarketTradingInfoData>> {
+    let market_trading_data = client
+        .send_json::<MarketTradingInfo>(client.http.get(client.url("/api/v1/info")))
+        .await?;
+
+    if !market_trading_data.success {
+        return Err(BotError::rejected("Invalid Market Data").into());
+    }
+
+    Ok(market_trading_data.data)
+}
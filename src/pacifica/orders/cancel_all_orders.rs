@@ -0,0 +1,74 @@
+use chrono::Utc;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+use crate::{
+    error::bot_error::BotError,
+    pacifica::{
+        orders::place_order::sign_message,
+        structs::{
+            CancelAllOrders, CancelAllOrdersPayload, CancelAllOrdersResponse, SignatureHeader,
+        },
+    },
+    utils::venue_client::VenueClient,
+};
+
+/// Cancels every open order on `market_name`, or on all markets when it is
+/// `None`. Returns the number of orders cancelled.
+pub async fn cancel_all_pacifica_orders(
+    client: &VenueClient,
+    market_name: Option<&str>,
+    exclude_reduce_only: bool,
+    private_key: &str,
+    wallet_address: &str,
+) -> anyhow::Result<u64> {
+    let keypair = Keypair::from_base58_string(private_key);
+    let agent_wallet_address = keypair.pubkey().to_string();
+    let current_timestamp = Utc::now().timestamp_millis();
+
+    let signature_header = SignatureHeader {
+        timestamp: current_timestamp as u64,
+        expiry_window: 5000u64,
+        r#type: "cancel_all_orders".to_string(),
+    };
+
+    let signature_payload = CancelAllOrdersPayload {
+        all_symbols: market_name.is_none(),
+        exclude_reduce_only,
+        symbol: market_name.map(str::to_string),
+    };
+
+    let signature = sign_message(&signature_header, &signature_payload, &keypair).await?;
+
+    let cancel_all_orders = CancelAllOrders {
+        account: wallet_address.to_string(),
+        agent_wallet: agent_wallet_address,
+        signature,
+        timestamp: signature_header.timestamp,
+        expiry_window: signature_header.expiry_window,
+        all_symbols: signature_payload.all_symbols,
+        exclude_reduce_only: signature_payload.exclude_reduce_only,
+        symbol: signature_payload.symbol,
+    };
+
+    let response = client
+        .send_json::<CancelAllOrdersResponse>(
+            client
+                .http
+                .post(client.url("/api/v1/orders/cancel_all"))
+                .json(&cancel_all_orders),
+        )
+        .await?;
+
+    if !response.success {
+        return Err(BotError::Rejected {
+            code: response.code.map(|c| c.to_string()),
+            message: response
+                .error
+                .unwrap_or_else(|| String::from("Failed to cancel all orders")),
+            body: None,
+        }
+        .into());
+    }
+
+    Ok(response.data.map(|data| data.cancelled_count).unwrap_or(0))
+}
@@ -0,0 +1,95 @@
+use chrono::Utc;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+use crate::{
+    error::bot_error::BotError,
+    pacifica::{
+        orders::place_order::sign_message,
+        structs::{ActionResponse, CancelOrder, CancelOrderPayload, SignatureHeader},
+    },
+    utils::venue_client::VenueClient,
+};
+
+/// Cancels an open order. Numeric ids are treated as exchange order ids,
+/// anything else as the client order id the order was placed with.
+pub async fn cancel_pacifica_order(
+    client: &VenueClient,
+    market_name: &str,
+    order_id: &str,
+    private_key: &str,
+    wallet_address: &str,
+) -> anyhow::Result<()> {
+    send_cancel(
+        client,
+        "/api/v1/orders/cancel",
+        "cancel_order",
+        market_name,
+        order_id,
+        private_key,
+        wallet_address,
+    )
+    .await
+}
+
+/// Signs and sends a cancel for a regular or a stop order, which only differ in
+/// endpoint and signature type.
+pub async fn send_cancel(
+    client: &VenueClient,
+    path: &str,
+    signature_type: &str,
+    market_name: &str,
+    order_id: &str,
+    private_key: &str,
+    wallet_address: &str,
+) -> anyhow::Result<()> {
+    let keypair = Keypair::from_base58_string(private_key);
+    let agent_wallet_address = keypair.pubkey().to_string();
+    let current_timestamp = Utc::now().timestamp_millis();
+
+    let (order_id, client_order_id) = match order_id.parse::<u64>() {
+        Ok(order_id) => (Some(order_id), None),
+        Err(_) => (None, Some(order_id.to_string())),
+    };
+
+    let signature_header = SignatureHeader {
+        timestamp: current_timestamp as u64,
+        expiry_window: 5000u64,
+        r#type: signature_type.to_string(),
+    };
+
+    let signature_payload = CancelOrderPayload {
+        symbol: market_name.to_string(),
+        order_id,
+        client_order_id,
+    };
+
+    let signature = sign_message(&signature_header, &signature_payload, &keypair).await?;
+
+    let cancel_order = CancelOrder {
+        account: wallet_address.to_string(),
+        agent_wallet: agent_wallet_address,
+        signature,
+        timestamp: signature_header.timestamp,
+        expiry_window: signature_header.expiry_window,
+        symbol: signature_payload.symbol,
+        order_id: signature_payload.order_id,
+        client_order_id: signature_payload.client_order_id,
+    };
+
+    let response = client
+        .send_json::<ActionResponse>(client.http.post(client.url(path)).json(&cancel_order))
+        .await?;
+
+    if !response.success {
+        return Err(BotError::Rejected {
+            code: response.code.map(|c| c.to_string()),
+            message: response
+                .error
+                .unwrap_or_else(|| String::from("Failed to cancel order")),
+            body: None,
+        }
+        .into());
+    }
+
+    Ok(())
+}
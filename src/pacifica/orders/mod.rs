@@ -1 +1,5 @@
+pub mod cancel_all_orders;
+pub mod cancel_order;
+pub mod place_limit_order;
 pub mod place_order;
+pub mod stop_orders;
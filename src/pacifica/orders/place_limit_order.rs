@@ -0,0 +1,101 @@
+use chrono::Utc;
+use rust_decimal::Decimal;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+use crate::{
+    pacifica::{
+        orders::place_order::{parse_order_ack, protective_orders, sign_message},
+        structs::{
+            CreateOrder, CreateOrderPayload, MarketInfoData, Side, SignatureHeader, TimeInForce,
+        },
+    },
+    utils::{
+        utils::{RoundingMode, round_to_min_change},
+        venue_client::VenueClient,
+    },
+    venue::structs::{OrderAck, OrderIntent},
+};
+
+/// Places a limit order at `price`. With `TimeInForce::Alo` the order is
+/// post-only and rejected by the venue instead of crossing the book. The
+/// protective orders of an opening `intent` are measured from `price`.
+pub async fn place_pacifica_limit_order(
+    client: &VenueClient,
+    market_name: &str,
+    side: Side,
+    qty: Decimal,
+    price: Decimal,
+    tif: TimeInForce,
+    intent: OrderIntent,
+    market_info: &MarketInfoData,
+    private_key: &str,
+    wallet_address: &str,
+) -> anyhow::Result<OrderAck> {
+    let keypair = Keypair::from_base58_string(private_key);
+    let agent_wallet_address = keypair.pubkey().to_string();
+    let current_timestamp = Utc::now().timestamp_millis();
+
+    // Round away from the book so a resting order never crosses the spread
+    let price_rounding = if matches!(side, Side::Bid) {
+        RoundingMode::Floor
+    } else {
+        RoundingMode::Ceil
+    };
+    let price = round_to_min_change(price, market_info.tick_size, Some(price_rounding));
+    let qty = round_to_min_change(qty, market_info.lot_size, Some(RoundingMode::Floor));
+    let reduce_only = intent == OrderIntent::Reduce;
+    let (take_profit, stop_loss) = protective_orders(
+        intent.tp_sl(),
+        matches!(side, Side::Bid),
+        price,
+        market_info.tick_size,
+    )?;
+
+    let signature_header = SignatureHeader {
+        timestamp: current_timestamp as u64,
+        expiry_window: 5000u64,
+        r#type: "create_order".to_string(),
+    };
+
+    let signature_payload = CreateOrderPayload {
+        symbol: market_name.to_string(),
+        price: price.to_string(),
+        amount: qty.to_string(),
+        side,
+        tif,
+        reduce_only,
+        client_order_id: uuid::Uuid::new_v4().to_string(),
+        take_profit,
+        stop_loss,
+    };
+
+    let signature = sign_message(&signature_header, &signature_payload, &keypair).await?;
+
+    let create_order = CreateOrder {
+        account: wallet_address.to_string(),
+        agent_wallet: agent_wallet_address,
+        signature,
+        timestamp: signature_header.timestamp,
+        expiry_window: signature_header.expiry_window,
+        symbol: signature_payload.symbol,
+        price: signature_payload.price,
+        amount: signature_payload.amount,
+        side: signature_payload.side,
+        tif: signature_payload.tif,
+        reduce_only: signature_payload.reduce_only,
+        client_order_id: signature_payload.client_order_id,
+        take_profit: signature_payload.take_profit,
+        stop_loss: signature_payload.stop_loss,
+    };
+
+    let response = client
+        .send_text(
+            client
+                .http
+                .post(client.url("/api/v1/orders/create"))
+                .json(&create_order),
+        )
+        .await?;
+
+    Ok(parse_order_ack(create_order.client_order_id, response)?)
+}
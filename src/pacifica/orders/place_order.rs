@@ -1,172 +1,165 @@
 use chrono::Utc;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Serialize;
 use solana_sdk::{signature::Keypair, signer::Signer};
 
 use crate::{
+    error::bot_error::BotError,
     pacifica::structs::{
-        MarketInfoData, PlaceOrder, Side, SignatureHeader, SignaturePayload, SignedMessage,
-        StopLoss, TakeProfit,
+        CreateOrderResponse, MarketInfoData, PlaceOrder, Side, SignatureHeader, SignaturePayload,
+        SignedMessage, StopLoss, TakeProfit,
     },
-    utils::utils::{RoundingMode, round_to_min_change_f64},
+    utils::{
+        utils::{RoundingMode, round_to_min_change},
+        venue_client::VenueClient,
+    },
+    venue::structs::{OrderAck, OrderIntent, OrderStatus, TpSl},
 };
 
-const SLIPPAGE: f64 = 0.01;
-
-pub async fn place_pacifica_order(
+/// Builds and signs a market order without sending it, so it can be submitted
+/// together with the other leg. Orders that reduce a position are reduce-only.
+pub async fn prepare_pacifica_order(
     market_name: &str,
     side: Side,
-    qty: f64,
+    qty: Decimal,
+    slippage: Decimal,
     market_info: &MarketInfoData,
-    tp_sl_included: bool,
+    intent: OrderIntent,
     private_key: &str,
     wallet_address: &str,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<PlaceOrder> {
     let keypair = Keypair::from_base58_string(&private_key);
     let agent_wallet_address = keypair.pubkey().to_string();
     let current_timestamp = Utc::now().timestamp_millis();
 
-    let market_price = market_info.mid.parse::<f64>()?;
-    let qty = round_to_min_change_f64(
-        qty,
-        market_info.lot_size.parse::<f64>()?,
-        Some(RoundingMode::Floor),
-    );
+    let qty = round_to_min_change(qty, market_info.lot_size, Some(RoundingMode::Floor));
     let is_buying = matches!(&side, &Side::Bid);
+    let reduce_only = intent == OrderIntent::Reduce;
+    // Pacifica expects the slippage in percent
+    let slippage_percent = (slippage * dec!(100)).normalize().to_string();
+
+    let (take_profit, stop_loss) = protective_orders(
+        intent.tp_sl(),
+        is_buying,
+        market_info.mid,
+        market_info.tick_size,
+    )?;
+
+    let signature_header = SignatureHeader {
+        timestamp: current_timestamp as u64,
+        expiry_window: 5000u64,
+        r#type: "create_market_order".to_string(),
+    };
+
+    let signature_payload = SignaturePayload {
+        symbol: market_name.to_string(),
+        side,
+        reduce_only,
+        amount: qty.to_string(),
+        slippage_percent: slippage_percent.to_string(),
+        client_order_id: uuid::Uuid::new_v4().to_string(),
+        take_profit,
+        stop_loss,
+    };
+
+    let signature = sign_message(&signature_header, &signature_payload, &keypair).await?;
+
+    Ok(PlaceOrder {
+        account: wallet_address.to_string(),
+        signature,
+        agent_wallet: agent_wallet_address,
+        timestamp: signature_header.timestamp,
+        expiry_window: signature_header.expiry_window,
+        symbol: signature_payload.symbol,
+        side: signature_payload.side,
+        reduce_only,
+        amount: signature_payload.amount,
+        slippage_percent,
+        client_order_id: signature_payload.client_order_id,
+        take_profit: signature_payload.take_profit,
+        stop_loss: signature_payload.stop_loss,
+    })
+}
+
+/// Take profit and stop loss orders attached to an opening order, with the
+/// distances of `tp_sl` measured from `reference_price`.
+pub fn protective_orders(
+    tp_sl: Option<TpSl>,
+    is_buying: bool,
+    reference_price: Decimal,
+    tick_size: Decimal,
+) -> anyhow::Result<(Option<TakeProfit>, Option<StopLoss>)> {
+    let Some(tp_sl) = tp_sl else {
+        return Ok((None, None));
+    };
+
+    let prices = tp_sl.prices(is_buying, reference_price, tick_size)?;
+    // Without an offset the triggered orders are sent as market orders
+    let with_limit = tp_sl.limit_offset > 0.0;
 
-    if tp_sl_included {
-        let rounding_mode = if is_buying {
-            RoundingMode::Floor
-        } else {
-            RoundingMode::Ceil
-        };
-
-        let take_profit_price = round_to_min_change_f64(
-            if is_buying {
-                market_price * 1.05
-            } else {
-                market_price * 0.95
-            },
-            market_info.tick_size.parse::<f64>()?,
-            Some(rounding_mode),
-        );
-        let stop_loss_price = round_to_min_change_f64(
-            if is_buying {
-                market_price * 0.95
-            } else {
-                market_price * 1.05
-            },
-            market_info.tick_size.parse::<f64>()?,
-            Some(rounding_mode),
-        );
-
-        let signature_header = SignatureHeader {
-            timestamp: current_timestamp as u64,
-            expiry_window: 5000u64,
-            r#type: "create_market_order".to_string(),
-        };
-
-        let signature_payload = SignaturePayload {
-            symbol: market_name.to_string(),
-            side: side,
-            reduce_only: false,
-            amount: qty.to_string(),
-            slippage_percent: SLIPPAGE.to_string(),
+    Ok((
+        Some(TakeProfit {
+            stop_price: prices.take_profit_trigger.to_string(),
+            limit_price: with_limit.then(|| prices.take_profit_limit.to_string()),
             client_order_id: uuid::Uuid::new_v4().to_string(),
-            take_profit: Some(TakeProfit {
-                stop_price: take_profit_price.to_string(),
-                client_order_id: uuid::Uuid::new_v4().to_string(),
-            }),
-            stop_loss: Some(StopLoss {
-                stop_price: stop_loss_price.to_string(),
-                client_order_id: uuid::Uuid::new_v4().to_string(),
-            }),
-        };
-
-        let signature = sign_message(&signature_header, &signature_payload, &keypair).await?;
-
-        let place_order = PlaceOrder {
-            account: wallet_address.to_string(),
-            signature: signature,
-            agent_wallet: agent_wallet_address,
-            timestamp: signature_header.timestamp,
-            expiry_window: signature_header.expiry_window,
-            symbol: signature_payload.symbol,
-            side: signature_payload.side,
-            reduce_only: false,
-            amount: signature_payload.amount,
-            slippage_percent: SLIPPAGE.to_string(),
-            client_order_id: signature_payload.client_order_id,
-            take_profit: signature_payload.take_profit,
-            stop_loss: signature_payload.stop_loss,
-        };
-
-        let client = reqwest::Client::new();
-        let response = client
-            .post("https://api.pacifica.fi/api/v1/orders/create_market")
-            .json(&place_order)
-            .send()
-            .await?;
-
-        println!("Response: {:?}", response);
-
-        if response.status().is_success() {
-            return Ok(());
-        } else {
-            return Err(anyhow::anyhow!("Failed to place order"));
+        }),
+        Some(StopLoss {
+            stop_price: prices.stop_loss_trigger.to_string(),
+            limit_price: with_limit.then(|| prices.stop_loss_limit.to_string()),
+            client_order_id: uuid::Uuid::new_v4().to_string(),
+        }),
+    ))
+}
+
+/// Sends an order built by `prepare_pacifica_order`.
+pub async fn submit_pacifica_order(
+    client: &VenueClient,
+    place_order: &PlaceOrder,
+) -> anyhow::Result<OrderAck> {
+    let response = client
+        .send_text(
+            client
+                .http
+                .post(client.url("/api/v1/orders/create_market"))
+                .json(place_order),
+        )
+        .await?;
+
+    Ok(parse_order_ack(
+        place_order.client_order_id.to_string(),
+        response,
+    )?)
+}
+
+pub fn parse_order_ack(client_order_id: String, body: String) -> Result<OrderAck, BotError> {
+    let response = serde_json::from_str::<CreateOrderResponse>(&body)
+        .map_err(|e| BotError::parse("CreateOrderResponse", format!("{}: {}", e, body)))?;
+
+    let ack = if response.success {
+        OrderAck {
+            venue_order_id: response.data.map(|data| data.order_id.to_string()),
+            client_order_id,
+            status: OrderStatus::Accepted,
+            rejection_reason: None,
+            body,
         }
     } else {
-        let signature_header = SignatureHeader {
-            timestamp: current_timestamp as u64,
-            expiry_window: 5000u64,
-            r#type: "create_market_order".to_string(),
-        };
-
-        let signature_payload = SignaturePayload {
-            symbol: market_name.to_string(),
-            side: side,
-            reduce_only: true,
-            amount: qty.to_string(),
-            slippage_percent: SLIPPAGE.to_string(),
-            client_order_id: uuid::Uuid::new_v4().to_string(),
-            take_profit: None,
-            stop_loss: None,
-        };
-
-        let signature = sign_message(&signature_header, &signature_payload, &keypair).await?;
-
-        let place_order = PlaceOrder {
-            account: wallet_address.to_string(),
-            signature: signature,
-            agent_wallet: agent_wallet_address,
-            timestamp: signature_header.timestamp,
-            expiry_window: signature_header.expiry_window,
-            symbol: signature_payload.symbol,
-            side: signature_payload.side,
-            reduce_only: true,
-            amount: signature_payload.amount,
-            slippage_percent: SLIPPAGE.to_string(),
-            client_order_id: signature_payload.client_order_id,
-            take_profit: signature_payload.take_profit,
-            stop_loss: signature_payload.stop_loss,
-        };
-
-        let client = reqwest::Client::new();
-        let response = client
-            .post("https://api.pacifica.fi/api/v1/orders/create_market")
-            .json(&place_order)
-            .send()
-            .await?;
-
-        if response.status().is_success() {
-            return Ok(());
-        } else {
-            return Err(anyhow::anyhow!("Failed to place order"));
+        OrderAck {
+            venue_order_id: None,
+            client_order_id,
+            status: OrderStatus::Rejected,
+            rejection_reason: response.error,
+            body,
         }
-    }
+    };
+
+    ack.accepted()
 }
 
-pub async fn sign_message(
+pub async fn sign_message<T: Serialize + Clone>(
     header: &SignatureHeader,
-    payload: &SignaturePayload,
+    payload: &T,
     keypair: &Keypair,
 ) -> Result<String, anyhow::Error> {
     let message = SignedMessage {
@@ -181,7 +174,7 @@ pub async fn sign_message(
     Ok(bs58::encode(signature).into_string())
 }
 
-pub fn sort_json_object(message: &SignedMessage) -> Result<String, anyhow::Error> {
+pub fn sort_json_object<T: Serialize>(message: &SignedMessage<T>) -> Result<String, anyhow::Error> {
     // Serialize to JSON value
     let json_value = serde_json::to_value(message)?;
 
@@ -0,0 +1,157 @@
+use chrono::Utc;
+use rust_decimal::Decimal;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+use crate::{
+    pacifica::{
+        orders::{
+            cancel_order::send_cancel,
+            place_order::{parse_order_ack, sign_message},
+        },
+        structs::{
+            CreateStopOrder, CreateStopOrderPayload, MarketInfoData, Side, SignatureHeader,
+            StopOrder,
+        },
+    },
+    utils::{
+        utils::{RoundingMode, round_to_min_change},
+        venue_client::VenueClient,
+    },
+    venue::structs::{OrderAck, TpSl},
+};
+
+/// Places a stop order that triggers at `stop_price`, as a limit order at
+/// `limit_price` when given and as a market order otherwise. Used for take
+/// profit and stop loss orders managed separately from the position.
+pub async fn create_pacifica_stop_order(
+    client: &VenueClient,
+    market_name: &str,
+    side: Side,
+    qty: Decimal,
+    stop_price: Decimal,
+    limit_price: Option<Decimal>,
+    reduce_only: bool,
+    market_info: &MarketInfoData,
+    private_key: &str,
+    wallet_address: &str,
+) -> anyhow::Result<OrderAck> {
+    let keypair = Keypair::from_base58_string(private_key);
+    let agent_wallet_address = keypair.pubkey().to_string();
+    let current_timestamp = Utc::now().timestamp_millis();
+
+    let qty = round_to_min_change(qty, market_info.lot_size, Some(RoundingMode::Floor));
+    let stop_price = round_to_min_change(stop_price, market_info.tick_size, None);
+    let limit_price = limit_price
+        .map(|price| round_to_min_change(price, market_info.tick_size, None).to_string());
+
+    let signature_header = SignatureHeader {
+        timestamp: current_timestamp as u64,
+        expiry_window: 5000u64,
+        r#type: "create_stop_order".to_string(),
+    };
+
+    let signature_payload = CreateStopOrderPayload {
+        symbol: market_name.to_string(),
+        side,
+        reduce_only,
+        stop_order: StopOrder {
+            stop_price: stop_price.to_string(),
+            limit_price,
+            client_order_id: uuid::Uuid::new_v4().to_string(),
+            amount: qty.to_string(),
+        },
+    };
+
+    let signature = sign_message(&signature_header, &signature_payload, &keypair).await?;
+
+    let create_stop_order = CreateStopOrder {
+        account: wallet_address.to_string(),
+        agent_wallet: agent_wallet_address,
+        signature,
+        timestamp: signature_header.timestamp,
+        expiry_window: signature_header.expiry_window,
+        symbol: signature_payload.symbol,
+        side: signature_payload.side,
+        reduce_only: signature_payload.reduce_only,
+        stop_order: signature_payload.stop_order,
+    };
+
+    let response = client
+        .send_text(
+            client
+                .http
+                .post(client.url("/api/v1/orders/stop/create"))
+                .json(&create_stop_order),
+        )
+        .await?;
+
+    Ok(parse_order_ack(
+        create_stop_order.stop_order.client_order_id,
+        response,
+    )?)
+}
+
+/// Places a reduce-only take profit and stop loss, as two stop orders, closing
+/// `qty` of a position opened with `side`. The distances of `tp_sl` are
+/// measured from the mid price, like on opening orders.
+pub async fn place_pacifica_tp_sl(
+    client: &VenueClient,
+    market_name: &str,
+    side: Side,
+    qty: Decimal,
+    tp_sl: TpSl,
+    market_info: &MarketInfoData,
+    private_key: &str,
+    wallet_address: &str,
+) -> anyhow::Result<Vec<OrderAck>> {
+    let is_buying = matches!(side, Side::Bid);
+    let closing_side = if is_buying { Side::Ask } else { Side::Bid };
+    let prices = tp_sl.prices(is_buying, market_info.mid, market_info.tick_size)?;
+    // Without an offset the triggered orders are sent as market orders
+    let with_limit = tp_sl.limit_offset > 0.0;
+
+    let mut acks = Vec::new();
+    for (stop_price, limit_price) in [
+        (prices.take_profit_trigger, prices.take_profit_limit),
+        (prices.stop_loss_trigger, prices.stop_loss_limit),
+    ] {
+        acks.push(
+            create_pacifica_stop_order(
+                client,
+                market_name,
+                closing_side.clone(),
+                qty,
+                stop_price,
+                with_limit.then_some(limit_price),
+                true,
+                market_info,
+                private_key,
+                wallet_address,
+            )
+            .await?,
+        );
+    }
+
+    Ok(acks)
+}
+
+/// Cancels a stop order, for example a take profit left behind after the
+/// position was closed. Ids are handled like in `cancel_pacifica_order`.
+pub async fn cancel_pacifica_stop_order(
+    client: &VenueClient,
+    market_name: &str,
+    order_id: &str,
+    private_key: &str,
+    wallet_address: &str,
+) -> anyhow::Result<()> {
+    send_cancel(
+        client,
+        "/api/v1/orders/stop/cancel",
+        "cancel_stop_order",
+        market_name,
+        order_id,
+        private_key,
+        wallet_address,
+    )
+    .await
+}
@@ -1,3 +1,4 @@
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Debug)]
@@ -8,22 +9,28 @@ pub struct MarketPricesInfo {
 
 #[derive(Deserialize, Debug)]
 pub struct MarketPricesInfoData {
-    pub mid: String,
-    pub next_funding: String,
+    pub mid: Decimal,
+    pub mark: Decimal,
+    pub next_funding: Decimal,
     pub symbol: String,
 }
 
 #[derive(Deserialize, Debug)]
 pub struct MarketInfoData {
-    pub mid: String,
-    pub next_funding: String,
+    pub mid: Decimal,
+    pub mark: Decimal,
+    pub next_funding: Decimal,
     pub symbol: String,
-    pub tick_size: String,
-    pub min_tick: String,
-    pub max_tick: String,
-    pub lot_size: String,
-    pub min_order_size: String,
-    pub max_order_size: String,
+    pub tick_size: Decimal,
+    pub min_tick: Decimal,
+    pub max_tick: Decimal,
+    pub lot_size: Decimal,
+    pub min_order_size: Decimal,
+    pub max_order_size: Decimal,
+    pub max_funding_rate: Option<Decimal>,
+    /// Hours between funding payments, measured from the funding history by
+    /// `PacificaVenue`.
+    pub funding_interval_hours: Option<f64>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -35,12 +42,15 @@ pub struct MarketTradingInfo {
 #[derive(Deserialize, Debug)]
 pub struct MarketTradingInfoData {
     pub symbol: String,
-    pub tick_size: String,
-    pub min_tick: String,
-    pub max_tick: String,
-    pub lot_size: String,
-    pub min_order_size: String,
-    pub max_order_size: String,
+    pub tick_size: Decimal,
+    pub min_tick: Decimal,
+    pub max_tick: Decimal,
+    pub lot_size: Decimal,
+    pub min_order_size: Decimal,
+    pub max_order_size: Decimal,
+    /// Largest funding rate charged per period either way, when capped.
+    #[serde(default)]
+    pub max_funding_rate: Option<Decimal>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -102,25 +112,31 @@ pub struct SignaturePayload {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct TakeProfit {
     pub stop_price: String,
+    /// Limit price once triggered, a market order is sent when missing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit_price: Option<String>,
     pub client_order_id: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct StopLoss {
     pub stop_price: String,
+    /// Limit price once triggered, a market order is sent when missing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit_price: Option<String>,
     pub client_order_id: String,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
-pub struct SignedMessage {
+pub struct SignedMessage<T> {
     pub timestamp: u64,
     pub expiry_window: u64,
     #[serde(rename = "type")]
     pub r#type: String,
-    pub data: SignaturePayload,
+    pub data: T,
 }
 
-impl SignedMessage {
+impl<T: Serialize> SignedMessage<T> {
     pub fn into_string(self) -> String {
         serde_json::to_string(&self).unwrap()
     }
@@ -144,3 +160,192 @@ pub struct TradeableBalanceData {
     pub balance: String,
     pub available_to_spend: String,
 }
+
+#[derive(Deserialize, Debug)]
+pub struct CreateOrderResponse {
+    pub success: bool,
+    pub data: Option<CreateOrderResponseData>,
+    pub error: Option<String>,
+    pub code: Option<i64>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CreateOrderResponseData {
+    pub order_id: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CancelOrder {
+    pub account: String,
+    pub agent_wallet: String,
+    pub signature: String,
+    pub timestamp: u64,
+    pub expiry_window: u64,
+    pub symbol: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_order_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CancelOrderPayload {
+    pub symbol: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_order_id: Option<String>,
+}
+
+/// How long a Pacifica limit order stays on the book.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum TimeInForce {
+    /// Good till cancelled.
+    Gtc,
+    /// Immediate or cancel.
+    Ioc,
+    /// Add liquidity only, rejected instead of crossing the book (post-only).
+    Alo,
+    /// Top of book, placed at the best bid or ask.
+    Tob,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CreateOrder {
+    pub account: String,
+    pub agent_wallet: String,
+    pub signature: String,
+    pub timestamp: u64,
+    pub expiry_window: u64,
+    pub symbol: String,
+    pub price: String,
+    pub amount: String,
+    pub side: Side,
+    pub tif: TimeInForce,
+    pub reduce_only: bool,
+    pub client_order_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub take_profit: Option<TakeProfit>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_loss: Option<StopLoss>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreateOrderPayload {
+    pub symbol: String,
+    pub price: String,
+    pub amount: String,
+    pub side: Side,
+    pub tif: TimeInForce,
+    pub reduce_only: bool,
+    pub client_order_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub take_profit: Option<TakeProfit>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_loss: Option<StopLoss>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CancelAllOrders {
+    pub account: String,
+    pub agent_wallet: String,
+    pub signature: String,
+    pub timestamp: u64,
+    pub expiry_window: u64,
+    pub all_symbols: bool,
+    pub exclude_reduce_only: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CancelAllOrdersPayload {
+    pub all_symbols: bool,
+    pub exclude_reduce_only: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CancelAllOrdersResponse {
+    pub success: bool,
+    pub data: Option<CancelAllOrdersResponseData>,
+    pub error: Option<String>,
+    pub code: Option<i64>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CancelAllOrdersResponseData {
+    pub cancelled_count: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CreateStopOrder {
+    pub account: String,
+    pub agent_wallet: String,
+    pub signature: String,
+    pub timestamp: u64,
+    pub expiry_window: u64,
+    pub symbol: String,
+    pub side: Side,
+    pub reduce_only: bool,
+    pub stop_order: StopOrder,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreateStopOrderPayload {
+    pub symbol: String,
+    pub side: Side,
+    pub reduce_only: bool,
+    pub stop_order: StopOrder,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StopOrder {
+    pub stop_price: String,
+    /// Limit price once triggered, a market order is sent when missing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit_price: Option<String>,
+    pub client_order_id: String,
+    pub amount: String,
+}
+
+/// Answer to requests that do not return any data, such as cancels.
+#[derive(Deserialize, Debug)]
+pub struct ActionResponse {
+    pub success: bool,
+    pub error: Option<String>,
+    pub code: Option<i64>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Klines {
+    pub success: bool,
+    #[serde(default)]
+    pub data: Vec<KlineData>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct FundingHistoryResponse {
+    pub success: bool,
+    #[serde(default)]
+    pub data: Vec<FundingRateData>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct FundingRateData {
+    pub funding_rate: Decimal,
+    /// Time the rate was paid in epoch milliseconds.
+    pub created_at: u64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct KlineData {
+    /// Candle start in epoch milliseconds.
+    pub t: u64,
+    pub o: Decimal,
+    pub h: Decimal,
+    pub l: Decimal,
+    pub c: Decimal,
+}
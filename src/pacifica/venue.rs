@@ -0,0 +1,291 @@
+use async_trait::async_trait;
+
+use crate::{
+    error::bot_error::{BotError, decimal_to_f64, f64_to_decimal, parse_number},
+    funding::funding_interval_hours::funding_interval_hours,
+    pacifica::{
+        account::{
+            get_open_positions::get_pacifica_open_positions,
+            get_tradeable_balance::get_pacifica_tradeable_balance,
+        },
+        markets::{
+            get_candles::get_pacifica_candles, get_funding_history::get_pacifica_funding_history,
+            get_market_data::get_pacifica_market_data,
+        },
+        orders::{
+            cancel_all_orders::cancel_all_pacifica_orders,
+            cancel_order::cancel_pacifica_order,
+            place_limit_order::place_pacifica_limit_order,
+            place_order::{prepare_pacifica_order, submit_pacifica_order},
+            stop_orders::place_pacifica_tp_sl,
+        },
+        structs::{
+            KlineData, MarketInfoData, OpenPositionData, PlaceOrder, Side as PacificaSide,
+            TimeInForce, TradeableBalanceData,
+        },
+    },
+    utils::venue_client::VenueClient,
+    venue::{
+        perp_venue::PerpVenue,
+        structs::{
+            Balance, Candle, FeeRates, MarketSnapshot, OrderAck, OrderIntent, Position,
+            PositionSide, Side, TpSl,
+        },
+    },
+};
+
+/// Pacifica pays funding every hour unless its history shows otherwise.
+const DEFAULT_FUNDING_INTERVAL_HOURS: f64 = 1.0;
+/// Payments read to measure the funding interval.
+const FUNDING_HISTORY_LIMIT: u32 = 24;
+
+pub struct PacificaVenue {
+    pub client: VenueClient,
+    pub private_key: String,
+    pub wallet_address: String,
+    /// Rates of the account's fee tier, which the API does not publish.
+    pub fees: FeeRates,
+}
+
+#[async_trait]
+impl PerpVenue for PacificaVenue {
+    type MarketData = MarketInfoData;
+    type PreparedOrder = PlaceOrder;
+
+    fn name(&self) -> &str {
+        "Pacifica"
+    }
+
+    async fn get_market_data(&self, market_name: &str) -> anyhow::Result<MarketInfoData> {
+        let mut market = get_pacifica_market_data(&self.client, market_name).await?;
+
+        let history =
+            get_pacifica_funding_history(&self.client, market_name, FUNDING_HISTORY_LIMIT).await?;
+        market.funding_interval_hours = funding_interval_hours(
+            &history
+                .iter()
+                .map(|rate| rate.created_at)
+                .collect::<Vec<_>>(),
+        );
+
+        Ok(market)
+    }
+
+    fn market_snapshot(&self, market: &MarketInfoData) -> anyhow::Result<MarketSnapshot> {
+        MarketSnapshot::try_from(market)
+    }
+
+    async fn get_open_positions(&self) -> anyhow::Result<Vec<Position>> {
+        get_pacifica_open_positions(&self.client, &self.wallet_address)
+            .await?
+            .iter()
+            .map(Position::try_from)
+            .collect()
+    }
+
+    async fn get_tradeable_balance(&self) -> anyhow::Result<Balance> {
+        Balance::try_from(
+            &get_pacifica_tradeable_balance(&self.client, &self.wallet_address).await?,
+        )
+    }
+
+    async fn get_fees(&self, _market_name: &str) -> anyhow::Result<FeeRates> {
+        Ok(self.fees)
+    }
+
+    async fn get_candles(&self, market_name: &str, limit: usize) -> anyhow::Result<Vec<Candle>> {
+        get_pacifica_candles(&self.client, market_name, limit)
+            .await?
+            .iter()
+            .map(Candle::try_from)
+            .collect()
+    }
+
+    async fn prepare_order(
+        &self,
+        market_name: &str,
+        market: &MarketInfoData,
+        side: Side,
+        qty: f64,
+        slippage: f64,
+        intent: OrderIntent,
+    ) -> anyhow::Result<PlaceOrder> {
+        prepare_pacifica_order(
+            market_name,
+            side.into(),
+            f64_to_decimal(qty, "qty")?,
+            f64_to_decimal(slippage, "slippage")?,
+            market,
+            intent,
+            &self.private_key,
+            &self.wallet_address,
+        )
+        .await
+    }
+
+    async fn submit_order(&self, order: PlaceOrder) -> anyhow::Result<OrderAck> {
+        submit_pacifica_order(&self.client, &order).await
+    }
+
+    async fn place_limit_order(
+        &self,
+        market_name: &str,
+        market: &MarketInfoData,
+        side: Side,
+        qty: f64,
+        price: f64,
+        tp_sl: Option<TpSl>,
+    ) -> anyhow::Result<OrderAck> {
+        place_pacifica_limit_order(
+            &self.client,
+            market_name,
+            side.into(),
+            f64_to_decimal(qty, "qty")?,
+            f64_to_decimal(price, "price")?,
+            TimeInForce::Alo,
+            OrderIntent::Open(tp_sl),
+            market,
+            &self.private_key,
+            &self.wallet_address,
+        )
+        .await
+    }
+
+    async fn place_tp_sl(
+        &self,
+        market_name: &str,
+        market: &MarketInfoData,
+        side: Side,
+        qty: f64,
+        tp_sl: TpSl,
+    ) -> anyhow::Result<()> {
+        place_pacifica_tp_sl(
+            &self.client,
+            market_name,
+            side.into(),
+            f64_to_decimal(qty, "qty")?,
+            tp_sl,
+            market,
+            &self.private_key,
+            &self.wallet_address,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn cancel_order(&self, market_name: &str, order_id: &str) -> anyhow::Result<()> {
+        cancel_pacifica_order(
+            &self.client,
+            market_name,
+            order_id,
+            &self.private_key,
+            &self.wallet_address,
+        )
+        .await
+    }
+
+    async fn cancel_all_orders(&self, market_name: &str) -> anyhow::Result<()> {
+        let cancelled_count = cancel_all_pacifica_orders(
+            &self.client,
+            Some(market_name),
+            false,
+            &self.private_key,
+            &self.wallet_address,
+        )
+        .await?;
+        if cancelled_count > 0 {
+            println!(
+                "Cancelled {} open orders on {} on Pacifica",
+                cancelled_count, market_name
+            );
+        }
+        Ok(())
+    }
+}
+
+impl From<Side> for PacificaSide {
+    fn from(side: Side) -> Self {
+        match side {
+            Side::Buy => PacificaSide::Bid,
+            Side::Sell => PacificaSide::Ask,
+        }
+    }
+}
+
+impl TryFrom<&MarketInfoData> for MarketSnapshot {
+    type Error = anyhow::Error;
+
+    // Pacifica only publishes a mid price, so it stands in for both sides of the book.
+    fn try_from(market: &MarketInfoData) -> Result<Self, Self::Error> {
+        let mid = decimal_to_f64(market.mid, "mid")?;
+
+        Ok(MarketSnapshot {
+            market: market.symbol.to_string(),
+            bid: mid,
+            ask: mid,
+            mark: decimal_to_f64(market.mark, "mark")?,
+            funding_rate: decimal_to_f64(market.next_funding, "next_funding")?,
+            funding_interval_hours: market
+                .funding_interval_hours
+                .unwrap_or(DEFAULT_FUNDING_INTERVAL_HOURS),
+            max_funding_rate: market
+                .max_funding_rate
+                .map(|rate| decimal_to_f64(rate, "max_funding_rate"))
+                .transpose()?,
+            // Paid on the interval boundary, the time is not published
+            next_funding_time: None,
+            lot_size: decimal_to_f64(market.lot_size, "lot_size")?,
+            // Order size limits are published in USD
+            max_order_value: Some(decimal_to_f64(market.max_order_size, "max_order_size")?),
+            max_position_value: None,
+        })
+    }
+}
+
+impl TryFrom<&KlineData> for Candle {
+    type Error = anyhow::Error;
+
+    fn try_from(kline: &KlineData) -> Result<Self, Self::Error> {
+        Ok(Candle {
+            open_time: kline.t,
+            high: decimal_to_f64(kline.h, "h")?,
+            low: decimal_to_f64(kline.l, "l")?,
+            close: decimal_to_f64(kline.c, "c")?,
+        })
+    }
+}
+
+impl TryFrom<&OpenPositionData> for Position {
+    type Error = anyhow::Error;
+
+    fn try_from(position: &OpenPositionData) -> Result<Self, Self::Error> {
+        // Positions are reported with the side of the order that opened them.
+        let side = match position.side.as_str() {
+            "bid" => PositionSide::Long,
+            "ask" => PositionSide::Short,
+            other => other
+                .parse::<PositionSide>()
+                .map_err(|_| BotError::parse("side", format!("unknown position side {}", other)))?,
+        };
+
+        Ok(Position::new(
+            position.symbol.to_string(),
+            side,
+            parse_number(&position.amount, "amount")?,
+            parse_number(&position.entry_price, "entry_price")?,
+            position.created_at,
+        ))
+    }
+}
+
+impl TryFrom<&TradeableBalanceData> for Balance {
+    type Error = anyhow::Error;
+
+    fn try_from(balance: &TradeableBalanceData) -> Result<Self, Self::Error> {
+        Ok(Balance {
+            balance: parse_number(&balance.balance, "balance")?,
+            available_for_trade: parse_number(&balance.available_to_spend, "available_to_spend")?,
+        })
+    }
+}
@@ -0,0 +1,52 @@
+use crate::{
+    config::structs::{AllocationConfig, MarketPair, PairLeg},
+    error::bot_error::{BotError, SkipReason},
+    portfolio::structs::Portfolio,
+};
+
+/// Checks that a new pair fits in the portfolio and returns the largest
+/// notional in USD per leg it may open. Pairs that already hold a position
+/// and pairs over the open pair limit are skipped.
+pub fn check_limits(
+    portfolio: &Portfolio,
+    pair: &MarketPair,
+    leg_a: &PairLeg,
+    leg_b: &PairLeg,
+    allocation: &AllocationConfig,
+) -> anyhow::Result<f64> {
+    if portfolio.holds(leg_a, leg_b) {
+        return Err(BotError::Skip(SkipReason::AlreadyHeld {
+            market: leg_a.market.clone(),
+        })
+        .into());
+    }
+
+    let open = portfolio.open_pairs();
+    if allocation.max_open_pairs > 0 && open >= allocation.max_open_pairs {
+        return Err(BotError::Skip(SkipReason::MaxOpenPairs {
+            open,
+            limit: allocation.max_open_pairs,
+        })
+        .into());
+    }
+
+    let mut headroom = f64::INFINITY;
+
+    if allocation.max_market_exposure > 0.0 {
+        headroom = headroom.min(allocation.max_market_exposure);
+    }
+
+    if allocation.max_total_exposure > 0.0 {
+        let held = portfolio.notional_a().max(portfolio.notional_b());
+        headroom = headroom.min(allocation.max_total_exposure - held);
+    }
+
+    // Margin already locked is the part of the equity not available to trade
+    for balance in [&portfolio.balance_a, &portfolio.balance_b] {
+        let locked = balance.balance - balance.available_for_trade;
+        let margin_left = allocation.max_margin_utilization * balance.balance - locked;
+        headroom = headroom.min(margin_left * pair.sizing.leverage);
+    }
+
+    Ok(headroom.max(0.0))
+}
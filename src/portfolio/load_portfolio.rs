@@ -0,0 +1,21 @@
+use crate::{portfolio::structs::Portfolio, venue::perp_venue::PerpVenue};
+
+/// Reads the open positions and collateral of both venues.
+pub async fn load_portfolio<A: PerpVenue, B: PerpVenue>(
+    venue_a: &A,
+    venue_b: &B,
+) -> anyhow::Result<Portfolio> {
+    let (positions_a, positions_b, balance_a, balance_b) = tokio::try_join!(
+        venue_a.get_open_positions(),
+        venue_b.get_open_positions(),
+        venue_a.get_tradeable_balance(),
+        venue_b.get_tradeable_balance()
+    )?;
+
+    Ok(Portfolio {
+        positions_a,
+        positions_b,
+        balance_a,
+        balance_b,
+    })
+}
@@ -0,0 +1,3 @@
+pub mod check_limits;
+pub mod load_portfolio;
+pub mod structs;
@@ -0,0 +1,83 @@
+use chrono::Utc;
+
+use crate::{
+    config::structs::PairLeg,
+    sizing::structs::PositionSize,
+    venue::structs::{Balance, Position, PositionSide, Side},
+};
+
+/// Open positions and collateral of both venues. The cycle planner adds the
+/// pairs it plans so the later ones see the capital already taken.
+#[derive(Debug, Clone)]
+pub struct Portfolio {
+    pub positions_a: Vec<Position>,
+    pub positions_b: Vec<Position>,
+    pub balance_a: Balance,
+    pub balance_b: Balance,
+}
+
+impl Portfolio {
+    /// Whether either leg of the pair already holds a position.
+    pub fn holds(&self, leg_a: &PairLeg, leg_b: &PairLeg) -> bool {
+        self.positions_a.iter().any(|p| p.market == leg_a.market)
+            || self.positions_b.iter().any(|p| p.market == leg_b.market)
+    }
+
+    /// Number of pairs held, orphaned legs included.
+    pub fn open_pairs(&self) -> usize {
+        self.positions_a.len().max(self.positions_b.len())
+    }
+
+    /// Notional in USD held on venue A at entry prices.
+    pub fn notional_a(&self) -> f64 {
+        open_notional(&self.positions_a)
+    }
+
+    /// Notional in USD held on venue B at entry prices.
+    pub fn notional_b(&self) -> f64 {
+        open_notional(&self.positions_b)
+    }
+
+    /// Records a pair about to be opened at `price` with `side_a` on venue A.
+    pub fn add_pair(
+        &mut self,
+        leg_a: &PairLeg,
+        leg_b: &PairLeg,
+        side_a: Side,
+        price: f64,
+        size: &PositionSize,
+        leverage: f64,
+    ) {
+        let (position_side_a, position_side_b) = match side_a {
+            Side::Sell => (PositionSide::Short, PositionSide::Long),
+            Side::Buy => (PositionSide::Long, PositionSide::Short),
+        };
+        let now_millis = Utc::now().timestamp_millis() as u64;
+
+        self.positions_a.push(Position::new(
+            leg_a.market.clone(),
+            position_side_a,
+            size.quantity,
+            price,
+            now_millis,
+        ));
+        self.positions_b.push(Position::new(
+            leg_b.market.clone(),
+            position_side_b,
+            size.quantity,
+            price,
+            now_millis,
+        ));
+
+        let margin = size.notional / leverage;
+        self.balance_a.available_for_trade -= margin;
+        self.balance_b.available_for_trade -= margin;
+    }
+}
+
+fn open_notional(positions: &[Position]) -> f64 {
+    positions
+        .iter()
+        .map(|position| position.quantity() * position.entry_price)
+        .sum()
+}
@@ -0,0 +1,2 @@
+pub mod size_position;
+pub mod structs;
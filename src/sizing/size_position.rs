@@ -0,0 +1,65 @@
+use crate::{
+    config::structs::MarketPair,
+    sizing::structs::{PositionSize, SizeLimit},
+    venue::structs::{Balance, MarketSnapshot},
+};
+
+/// Sizes a new pair so both legs open the same notional. Starts from
+/// `buy_amount`, or a share of the smaller equity when sizing by equity,
+/// scales it down for small `edge` (net expected profit in percent of the
+/// notional) and caps it by the collateral of both venues and the market limits.
+pub fn size_position(
+    pair: &MarketPair,
+    balance_a: &Balance,
+    snapshot_a: &MarketSnapshot,
+    balance_b: &Balance,
+    snapshot_b: &MarketSnapshot,
+    edge: f64,
+) -> PositionSize {
+    let sizing = &pair.sizing;
+
+    let mut notional = if sizing.equity_fraction > 0.0 {
+        balance_a.balance.min(balance_b.balance) * sizing.equity_fraction * sizing.leverage
+    } else {
+        pair.buy_amount
+    };
+    let mut limited_by = SizeLimit::Target;
+
+    if sizing.full_size_edge > 0.0 && edge < sizing.full_size_edge {
+        notional *= (edge / sizing.full_size_edge).max(0.0);
+        limited_by = SizeLimit::Edge;
+    }
+
+    let collateral = balance_a
+        .available_for_trade
+        .min(balance_b.available_for_trade)
+        * sizing.collateral_usage
+        * sizing.leverage;
+    if collateral < notional {
+        notional = collateral.max(0.0);
+        limited_by = SizeLimit::Collateral;
+    }
+
+    let market_limit = [
+        snapshot_a.max_order_value,
+        snapshot_a.max_position_value,
+        snapshot_b.max_order_value,
+        snapshot_b.max_position_value,
+    ]
+    .into_iter()
+    .flatten()
+    .fold(f64::INFINITY, f64::min);
+    if market_limit < notional {
+        notional = market_limit;
+        limited_by = SizeLimit::Market;
+    }
+
+    // Priced at the higher ask so neither leg goes over the notional
+    let price = snapshot_a.ask.max(snapshot_b.ask);
+
+    PositionSize {
+        notional,
+        quantity: notional / price,
+        limited_by,
+    }
+}
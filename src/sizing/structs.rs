@@ -0,0 +1,54 @@
+use std::fmt;
+
+/// Matched size of both legs of a new pair.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionSize {
+    /// Notional in USD opened on each leg.
+    pub notional: f64,
+    /// Quantity in base units, the same on both legs.
+    pub quantity: f64,
+    pub limited_by: SizeLimit,
+}
+
+impl PositionSize {
+    /// The size scaled down to at most `notional`.
+    pub fn capped(self, notional: f64, limited_by: SizeLimit) -> PositionSize {
+        if self.notional <= notional {
+            return self;
+        }
+
+        let notional = notional.max(0.0);
+        PositionSize {
+            notional,
+            quantity: self.quantity * notional / self.notional,
+            limited_by,
+        }
+    }
+}
+
+/// What set the size of a new pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeLimit {
+    /// The configured `buy_amount` or share of equity.
+    Target,
+    /// Scaled down because the expected edge is below `full_size_edge`.
+    Edge,
+    /// Collateral available on one of the venues.
+    Collateral,
+    /// Order or position size limit of one of the markets.
+    Market,
+    /// Exposure and margin caps of the allocation config.
+    Allocation,
+}
+
+impl fmt::Display for SizeLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SizeLimit::Target => write!(f, "target size"),
+            SizeLimit::Edge => write!(f, "expected edge"),
+            SizeLimit::Collateral => write!(f, "available collateral"),
+            SizeLimit::Market => write!(f, "market size limit"),
+            SizeLimit::Allocation => write!(f, "capital allocation"),
+        }
+    }
+}
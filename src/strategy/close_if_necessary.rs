@@ -0,0 +1,163 @@
+use anyhow::Context;
+use chrono::Utc;
+
+use crate::{
+    config::structs::{ExitPolicy, PairLeg},
+    funding::normalize_funding::normalize_funding,
+    strategy::{combine_errors::combine_errors, structs::ExitReason},
+    venue::{
+        perp_venue::PerpVenue,
+        structs::{MarketSnapshot, OrderIntent, Position, PositionSide},
+    },
+};
+
+const MILLIS_PER_HOUR: f64 = 3_600_000.0;
+
+/// Closes both legs of an open pair when one of the `exit` rules applies and
+/// returns the rule that did. The pair is short on `venue_a` and long on
+/// `venue_b` when `open_position_a` is short, and the other way around.
+pub async fn close_if_necessary<A: PerpVenue, B: PerpVenue>(
+    venue_a: &A,
+    leg_a: &PairLeg,
+    open_position_a: &Position,
+    venue_b: &B,
+    leg_b: &PairLeg,
+    open_position_b: &Position,
+    exit: &ExitPolicy,
+) -> anyhow::Result<Option<ExitReason>> {
+    let market_name_a = leg_a.market.as_str();
+    let market_name_b = leg_b.market.as_str();
+    println!(
+        "Closing if necessary for market: {} and {}",
+        market_name_a, market_name_b
+    );
+    let market_a = venue_a.get_market_data(market_name_a).await?;
+    let market_b = venue_b.get_market_data(market_name_b).await?;
+    let snapshot_a = venue_a.market_snapshot(&market_a)?;
+    let snapshot_b = venue_b.market_snapshot(&market_b)?;
+
+    let now_millis = Utc::now().timestamp_millis() as u64;
+    let funding_a = normalize_funding(&snapshot_a, now_millis);
+    let funding_b = normalize_funding(&snapshot_b, now_millis);
+    println!("{} Funding: {}", venue_a.name(), funding_a);
+    println!("{} Funding: {}", venue_b.name(), funding_b);
+
+    // Positive while the venue the pair is short on still pays more funding
+    let favored_diff = match open_position_a.side {
+        PositionSide::Short => funding_a.hourly_rate - funding_b.hourly_rate,
+        PositionSide::Long => funding_b.hourly_rate - funding_a.hourly_rate,
+    } * 100.0;
+
+    let opened_at = open_position_a.opened_at.min(open_position_b.opened_at);
+    let held_hours = now_millis.saturating_sub(opened_at) as f64 / MILLIS_PER_HOUR;
+    let funding_interval_hours = snapshot_a
+        .funding_interval_hours
+        .max(snapshot_b.funding_interval_hours);
+    let funding_periods = held_hours / funding_interval_hours;
+
+    let notional = open_position_a.quantity() * open_position_a.entry_price;
+    let basis_pnl = (closing_pnl(open_position_a, &snapshot_a)
+        + closing_pnl(open_position_b, &snapshot_b))
+        / notional
+        * 100.0;
+
+    println!(
+        "Held {}h ({} funding periods), funding diff in favor {}%/h, basis PnL {}%",
+        held_hours, funding_periods, favored_diff, basis_pnl
+    );
+
+    let reason = if exit.max_holding_hours > 0.0 && held_hours >= exit.max_holding_hours {
+        ExitReason::MaxHoldingTime {
+            hours: held_hours,
+            limit: exit.max_holding_hours,
+        }
+    } else if exit.basis_take_profit > 0.0 && basis_pnl >= exit.basis_take_profit {
+        ExitReason::BasisTakeProfit {
+            pnl: basis_pnl,
+            target: exit.basis_take_profit,
+        }
+    } else if favored_diff < exit.exit_threshold
+        && funding_periods >= exit.min_funding_periods as f64
+    {
+        ExitReason::FundingBelowExit {
+            diff: favored_diff,
+            threshold: exit.exit_threshold,
+        }
+    } else {
+        return Ok(None);
+    };
+
+    println!(
+        "Closing {} on {} and {} on {}: {}",
+        market_name_a,
+        venue_a.name(),
+        market_name_b,
+        venue_b.name(),
+        reason
+    );
+
+    // Both legs are closed even when the other fails, a failed close must
+    // not leave the other leg open unhedged
+    let closed_a = venue_a
+        .place_order(
+            market_name_a,
+            &market_a,
+            open_position_a.side.closing_side(),
+            open_position_a.quantity(),
+            leg_a.slippage,
+            OrderIntent::Reduce,
+        )
+        .await
+        .with_context(|| format!("Failed to close {} on {}", market_name_a, venue_a.name()));
+    let closed_b = venue_b
+        .place_order(
+            market_name_b,
+            &market_b,
+            open_position_b.side.closing_side(),
+            open_position_b.quantity(),
+            leg_b.slippage,
+            OrderIntent::Reduce,
+        )
+        .await
+        .with_context(|| format!("Failed to close {} on {}", market_name_b, venue_b.name()));
+
+    // The TP/SL orders of the closed legs would otherwise stay on the book
+    let cancelled_a = cancel_leftovers(venue_a, market_name_a).await;
+    let cancelled_b = cancel_leftovers(venue_b, market_name_b).await;
+
+    combine_errors(
+        [
+            closed_a.map(drop),
+            closed_b.map(drop),
+            cancelled_a,
+            cancelled_b,
+        ]
+        .into_iter()
+        .filter_map(Result::err)
+        .collect(),
+    )?;
+
+    Ok(Some(reason))
+}
+
+/// Cancels the orders left on `market_name`, naming the market when it fails.
+async fn cancel_leftovers<V: PerpVenue>(venue: &V, market_name: &str) -> anyhow::Result<()> {
+    venue.cancel_all_orders(market_name).await.with_context(|| {
+        format!(
+            "Failed to cancel {} orders on {}",
+            market_name,
+            venue.name()
+        )
+    })
+}
+
+/// Profit of closing the position now: longs sell into the bid, shorts buy
+/// from the ask.
+fn closing_pnl(position: &Position, snapshot: &MarketSnapshot) -> f64 {
+    let exit_price = match position.side {
+        PositionSide::Long => snapshot.bid,
+        PositionSide::Short => snapshot.ask,
+    };
+
+    (exit_price - position.entry_price) * position.size
+}
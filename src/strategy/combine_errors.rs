@@ -0,0 +1,18 @@
+use anyhow::anyhow;
+
+/// Reports every failed step of a sequence that keeps going after a failure,
+/// so one error does not hide the others. A single error is returned as is.
+pub fn combine_errors(errors: Vec<anyhow::Error>) -> anyhow::Result<()> {
+    if errors.len() <= 1 {
+        return errors.into_iter().next().map_or(Ok(()), Err);
+    }
+
+    Err(anyhow!(
+        "{}",
+        errors
+            .iter()
+            .map(|e| format!("{:#}", e))
+            .collect::<Vec<_>>()
+            .join("; ")
+    ))
+}
@@ -0,0 +1,40 @@
+use crate::{
+    config::structs::{EntryMode, MarketPair},
+    strategy::structs::EdgeEstimate,
+    venue::structs::FeeRates,
+};
+
+/// Expected profit of opening `pair` with `buy_amount` on each leg and holding
+/// it for `expected_holding_hours`. `hourly_funding_diff` is the funding rate
+/// difference as a fraction per hour, `sell_price` and `buy_price` the prices
+/// the short and the long leg are entered at.
+pub fn estimate_edge(
+    pair: &MarketPair,
+    hourly_funding_diff: f64,
+    sell_price: f64,
+    buy_price: f64,
+    fees_a: &FeeRates,
+    fees_b: &FeeRates,
+) -> EdgeEstimate {
+    let notional = pair.buy_amount;
+
+    // Maker entries rest on venue A, every other fill crosses the book
+    let entry_fee_a = if pair.entry_mode == EntryMode::Maker {
+        fees_a.maker
+    } else {
+        fees_a.taker
+    };
+
+    let funding = hourly_funding_diff * pair.expected_holding_hours * notional;
+    let fees = (entry_fee_a + fees_a.taker + 2.0 * fees_b.taker) * notional;
+    let slippage = 4.0 * pair.estimated_slippage * notional;
+    let basis = (sell_price - buy_price) / buy_price * notional;
+
+    EdgeEstimate {
+        funding,
+        fees,
+        slippage,
+        basis,
+        net: funding - fees - slippage + basis,
+    }
+}
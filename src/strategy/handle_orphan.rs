@@ -0,0 +1,131 @@
+use anyhow::{Context, anyhow};
+
+use crate::{
+    config::structs::{OrphanPolicy, TpSlPolicy},
+    strategy::{combine_errors::combine_errors, resolve_tp_sl::resolve_tp_sl, structs::VenueLeg},
+    venue::{
+        perp_venue::PerpVenue,
+        structs::{OrderIntent, Position},
+    },
+};
+
+/// Applies the pair's orphan policy to a position whose counterpart on the
+/// hedge venue is missing. `contract_ratio` is the number of hedge venue
+/// contracts one orphan venue contract is worth, `tp_sl` protects a rehedged
+/// leg. Cancels are best-effort: every step is attempted and the failures are
+/// returned together.
+pub async fn handle_orphan<O: PerpVenue, H: PerpVenue>(
+    orphan: VenueLeg<'_, O>,
+    orphan_position: &Position,
+    hedge: VenueLeg<'_, H>,
+    contract_ratio: f64,
+    policy: OrphanPolicy,
+    tp_sl: &TpSlPolicy,
+) -> anyhow::Result<()> {
+    let VenueLeg {
+        venue: orphan_venue,
+        leg: orphan_leg,
+    } = orphan;
+    let VenueLeg {
+        venue: hedge_venue,
+        leg: hedge_leg,
+    } = hedge;
+    println!(
+        "Orphaned {:?} {} {} on {} has no {} position on {}",
+        orphan_position.side,
+        orphan_position.quantity(),
+        orphan_leg.market,
+        orphan_venue.name(),
+        hedge_leg.market,
+        hedge_venue.name()
+    );
+
+    match policy {
+        OrphanPolicy::Close => {
+            let closed = async {
+                let market = orphan_venue.get_market_data(&orphan_leg.market).await?;
+                orphan_venue
+                    .place_order(
+                        &orphan_leg.market,
+                        &market,
+                        orphan_position.side.closing_side(),
+                        orphan_position.quantity(),
+                        orphan_leg.slippage,
+                        OrderIntent::Reduce,
+                    )
+                    .await
+            }
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to close {} on {}",
+                    orphan_leg.market,
+                    orphan_venue.name()
+                )
+            });
+
+            // Leftover TP/SL orders of both legs must not open new positions
+            // later. The orphan keeps its own while it could not be closed.
+            let orphan_cancelled = match closed {
+                Ok(_) => cancel_leftovers(orphan_venue, &orphan_leg.market).await,
+                Err(e) => Err(e),
+            };
+            let hedge_cancelled = cancel_leftovers(hedge_venue, &hedge_leg.market).await;
+
+            combine_errors(
+                [orphan_cancelled, hedge_cancelled]
+                    .into_iter()
+                    .filter_map(Result::err)
+                    .collect(),
+            )
+        }
+        OrphanPolicy::Rehedge => {
+            let market = hedge_venue.get_market_data(&hedge_leg.market).await?;
+            let tp_sl = resolve_tp_sl(hedge_venue, &hedge_leg.market, &market, tp_sl).await?;
+            // Replaces the TP/SL orders the missing leg left behind. The orphan
+            // is hedged even when they could not be cancelled.
+            let cancelled = cancel_leftovers(hedge_venue, &hedge_leg.market).await;
+            let opened = hedge_venue
+                .place_order(
+                    &hedge_leg.market,
+                    &market,
+                    orphan_position.side.closing_side(),
+                    orphan_position.quantity() * contract_ratio,
+                    hedge_leg.slippage,
+                    OrderIntent::Open(tp_sl),
+                )
+                .await
+                .with_context(|| {
+                    format!(
+                        "Failed to rehedge {} on {}",
+                        hedge_leg.market,
+                        hedge_venue.name()
+                    )
+                });
+
+            combine_errors(
+                [cancelled, opened.map(drop)]
+                    .into_iter()
+                    .filter_map(Result::err)
+                    .collect(),
+            )
+        }
+        OrphanPolicy::Alert => Err(anyhow!(
+            "{} position on {} is not hedged on {}, left open",
+            orphan_leg.market,
+            orphan_venue.name(),
+            hedge_venue.name()
+        )),
+    }
+}
+
+/// Cancels the orders left on `market_name`, naming the market when it fails.
+async fn cancel_leftovers<V: PerpVenue>(venue: &V, market_name: &str) -> anyhow::Result<()> {
+    venue.cancel_all_orders(market_name).await.with_context(|| {
+        format!(
+            "Failed to cancel {} orders on {}",
+            market_name,
+            venue.name()
+        )
+    })
+}
@@ -0,0 +1,160 @@
+use tokio::time::{Duration, Instant, sleep};
+
+use crate::{
+    strategy::{
+        combine_errors::combine_errors, place_arb_order::log_order_ack,
+        position_size::position_size, protect_leg::protect_leg, structs::LegCtx,
+    },
+    venue::{
+        perp_venue::PerpVenue,
+        structs::{OrderIntent, Side, TpSl},
+    },
+};
+
+const FILL_POLL_INTERVAL_MILLIS: u64 = 1_000;
+
+/// Opens a pair by resting a post-only limit order on the maker venue at the
+/// best price on its own side of the book. Every fill is hedged right away with
+/// a market order on the hedge venue, and whatever has not filled when
+/// `timeout` runs out is cancelled. The maker order carries `tp_sl` itself,
+/// the hedge leg gets a single set for everything hedged once the maker order
+/// is gone.
+pub async fn place_maker_entry<M: PerpVenue, H: PerpVenue>(
+    maker: LegCtx<'_, M>,
+    hedge: LegCtx<'_, H>,
+    maker_side: Side,
+    qty: f64,
+    tp_sl: Option<TpSl>,
+    timeout: Duration,
+) -> anyhow::Result<()> {
+    let LegCtx {
+        venue: maker_venue,
+        leg: maker_leg,
+        market: maker_market,
+    } = maker;
+    let hedge_venue = hedge.venue;
+    let snapshot = maker_venue.market_snapshot(maker_market)?;
+    let hedge_lot_size = hedge_venue.market_snapshot(hedge.market)?.lot_size;
+    let price = match maker_side {
+        Side::Buy => snapshot.bid,
+        Side::Sell => snapshot.ask,
+    };
+
+    let start_size = position_size(maker_venue, &maker_leg.market).await?;
+    let ack = maker_venue
+        .place_limit_order(
+            &maker_leg.market,
+            maker_market,
+            maker_side,
+            qty,
+            price,
+            tp_sl,
+        )
+        .await?;
+    log_order_ack(maker_venue.name(), &maker_leg.market, &ack);
+
+    let deadline = Instant::now() + timeout;
+    let mut hedged = 0.0;
+    let watched = async {
+        loop {
+            sleep(Duration::from_millis(FILL_POLL_INTERVAL_MILLIS)).await;
+
+            let filled = (position_size(maker_venue, &maker_leg.market).await? - start_size).abs();
+            hedged += hedge_fill(
+                hedge,
+                maker_side.opposite(),
+                filled - hedged,
+                hedge_lot_size,
+            )
+            .await?;
+
+            if filled > qty - snapshot.lot_size || Instant::now() >= deadline {
+                return anyhow::Ok(());
+            }
+        }
+    }
+    .await;
+
+    // The rest is cancelled even when hedging failed, so nothing fills unhedged later
+    if let Some(order_id) = ack.venue_order_id.as_deref()
+        && let Err(e) = maker_venue.cancel_order(&maker_leg.market, order_id).await
+    {
+        println!(
+            "Failed to cancel maker order {} on {}: {}",
+            order_id,
+            maker_venue.name(),
+            e
+        );
+    }
+
+    // Fills can land between the last check and the cancel
+    let final_hedge = async {
+        watched?;
+        let filled = (position_size(maker_venue, &maker_leg.market).await? - start_size).abs();
+        hedged += hedge_fill(
+            hedge,
+            maker_side.opposite(),
+            filled - hedged,
+            hedge_lot_size,
+        )
+        .await?;
+        anyhow::Ok(filled)
+    }
+    .await;
+
+    // What was hedged is protected even when hedging stopped early
+    let protected = protect_leg(hedge, maker_side.opposite(), hedged, tp_sl).await;
+    let filled = match (final_hedge, protected) {
+        (Ok(filled), Ok(())) => filled,
+        (final_hedge, protected) => {
+            return combine_errors(
+                [final_hedge.err(), protected.err()]
+                    .into_iter()
+                    .flatten()
+                    .collect(),
+            );
+        }
+    };
+
+    println!(
+        "Maker entry on {}: {} of {} filled, {} hedged on {}",
+        maker_leg.market,
+        filled,
+        qty,
+        hedged,
+        hedge_venue.name()
+    );
+
+    Ok(())
+}
+
+/// Hedges whole lots of `unhedged` with a market order and returns the
+/// quantity sent. The order carries no TP/SL, the hedge leg is protected once
+/// it is complete.
+async fn hedge_fill<V: PerpVenue>(
+    hedge: LegCtx<'_, V>,
+    side: Side,
+    unhedged: f64,
+    lot_size: f64,
+) -> anyhow::Result<f64> {
+    let LegCtx { venue, leg, market } = hedge;
+    // The small epsilon keeps float noise from dropping a whole lot
+    let qty = ((unhedged / lot_size) + 1e-9).floor() * lot_size;
+    if qty <= 0.0 {
+        return Ok(0.0);
+    }
+
+    let ack = venue
+        .place_order(
+            &leg.market,
+            market,
+            side,
+            qty,
+            leg.slippage,
+            OrderIntent::Open(None),
+        )
+        .await?;
+    log_order_ack(venue.name(), &leg.market, &ack);
+
+    Ok(qty)
+}
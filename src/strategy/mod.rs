@@ -0,0 +1,15 @@
+pub mod close_if_necessary;
+pub mod combine_errors;
+pub mod estimate_edge;
+pub mod handle_orphan;
+pub mod maker_entry;
+pub mod place_arb_order;
+pub mod plan_entries;
+pub mod position_size;
+pub mod protect_leg;
+pub mod reconcile_legs;
+pub mod repair_imbalance;
+pub mod resolve_tp_sl;
+pub mod structs;
+pub mod twap_entry;
+pub mod unwind_leg;
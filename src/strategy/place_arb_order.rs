@@ -0,0 +1,335 @@
+use anyhow::anyhow;
+use chrono::Utc;
+use tokio::time::Duration;
+
+use crate::{
+    config::structs::{EntryMode, MarketPair, PairLeg},
+    error::bot_error::{BotError, SkipReason},
+    funding::normalize_funding::normalize_funding,
+    sizing::structs::PositionSize,
+    strategy::{
+        estimate_edge::estimate_edge,
+        maker_entry::place_maker_entry,
+        position_size::position_size,
+        reconcile_legs::reconcile_legs,
+        resolve_tp_sl::resolve_tp_sl,
+        structs::{LegCtx, Opportunity},
+        twap_entry::place_twap_entry,
+        unwind_leg::unwind_leg,
+    },
+    venue::{
+        perp_venue::PerpVenue,
+        structs::{OrderAck, OrderIntent, Side, TpSl},
+    },
+};
+
+/// Checks whether a pair is worth entering: prices close enough, a large
+/// enough funding rate difference and a positive expected profit after costs.
+pub async fn evaluate_pair<A: PerpVenue, B: PerpVenue>(
+    venue_a: &A,
+    leg_a: &PairLeg,
+    venue_b: &B,
+    leg_b: &PairLeg,
+    pair: &MarketPair,
+) -> anyhow::Result<Opportunity<A::MarketData, B::MarketData>> {
+    let market_name_a = leg_a.market.as_str();
+    let market_name_b = leg_b.market.as_str();
+    println!(
+        "Checking funding arb for market: {} and {}",
+        market_name_a, market_name_b
+    );
+    let market_a = venue_a.get_market_data(market_name_a).await?;
+    let market_b = venue_b.get_market_data(market_name_b).await?;
+    let snapshot_a = venue_a.market_snapshot(&market_a)?;
+    let snapshot_b = venue_b.market_snapshot(&market_b)?;
+
+    let now_millis = Utc::now().timestamp_millis() as u64;
+    let funding_a = normalize_funding(&snapshot_a, now_millis);
+    let funding_b = normalize_funding(&snapshot_b, now_millis);
+
+    // Hourly rates, so venues with different funding intervals compare like with like
+    let funding_rate_a = funding_a.hourly_rate * 100.0;
+    let funding_rate_b = funding_b.hourly_rate * 100.0;
+
+    let price_a = snapshot_a.bid;
+    let price_b = snapshot_b.bid;
+
+    let price_spread = price_spread_percent(price_a, price_b);
+
+    let funding_rate_diff = (funding_rate_a - funding_rate_b).abs();
+
+    println!("{} Funding: {}", venue_a.name(), funding_a);
+    println!("{} Funding: {}", venue_b.name(), funding_b);
+    println!("Price Spread: {}", price_spread);
+    println!(
+        "Funding Rate Diff: {}%/h, APR {}%",
+        funding_rate_diff,
+        (funding_a.apr - funding_b.apr).abs()
+    );
+
+    if price_spread > pair.price_spread_threshold {
+        return Err(BotError::Skip(SkipReason::PriceSpreadTooHigh {
+            spread: price_spread,
+            threshold: pair.price_spread_threshold,
+        })
+        .into());
+    }
+
+    if funding_rate_diff < pair.funding_rate_threshold {
+        return Err(BotError::Skip(SkipReason::FundingRateDiffTooLow {
+            diff: funding_rate_diff,
+            threshold: pair.funding_rate_threshold,
+        })
+        .into());
+    }
+
+    // SHORT on venue_a, LONG on venue_b when venue_a pays more funding
+    let side_a = if funding_rate_a > funding_rate_b {
+        Side::Sell
+    } else {
+        Side::Buy
+    };
+
+    let (fees_a, fees_b) = tokio::try_join!(
+        venue_a.get_fees(market_name_a),
+        venue_b.get_fees(market_name_b)
+    )?;
+    // The short leg sells into the bid, the long leg buys from the ask
+    let (sell_price, buy_price) = match side_a {
+        Side::Sell => (snapshot_a.bid, snapshot_b.ask),
+        Side::Buy => (snapshot_b.bid, snapshot_a.ask),
+    };
+    let edge = estimate_edge(
+        pair,
+        funding_rate_diff / 100.0,
+        sell_price,
+        buy_price,
+        &fees_a,
+        &fees_b,
+    );
+    println!(
+        "Expected edge over {}h: funding {}, fees {}, slippage {}, basis {}, net {}",
+        pair.expected_holding_hours, edge.funding, edge.fees, edge.slippage, edge.basis, edge.net
+    );
+
+    let required_edge = pair.min_net_edge / 100.0 * pair.buy_amount;
+    if edge.net <= required_edge {
+        return Err(BotError::Skip(SkipReason::NetEdgeTooLow {
+            net: edge.net,
+            required: required_edge,
+        })
+        .into());
+    }
+
+    Ok(Opportunity {
+        market_a,
+        market_b,
+        snapshot_a,
+        snapshot_b,
+        side_a,
+        edge,
+        net_return: edge.net / pair.buy_amount * 100.0,
+    })
+}
+
+/// Opens both legs of an evaluated pair with `size` on each of them and checks
+/// they ended up hedging each other.
+pub async fn open_pair<A: PerpVenue, B: PerpVenue>(
+    venue_a: &A,
+    leg_a: &PairLeg,
+    venue_b: &B,
+    leg_b: &PairLeg,
+    pair: &MarketPair,
+    opportunity: &Opportunity<A::MarketData, B::MarketData>,
+    size: PositionSize,
+) -> anyhow::Result<()> {
+    let market_name_a = leg_a.market.as_str();
+    let market_name_b = leg_b.market.as_str();
+    let market_a = &opportunity.market_a;
+    let market_b = &opportunity.market_b;
+    let side_a = opportunity.side_a;
+
+    println!(
+        "Position size: {} USD, {} per leg (limited by the {})",
+        size.notional, size.quantity, size.limited_by
+    );
+
+    let lot_size = opportunity
+        .snapshot_a
+        .lot_size
+        .max(opportunity.snapshot_b.lot_size);
+    if size.quantity < lot_size {
+        return Err(BotError::Skip(SkipReason::PositionTooSmall {
+            notional: size.notional,
+            minimum: lot_size * opportunity.snapshot_a.ask.max(opportunity.snapshot_b.ask),
+            limited_by: size.limited_by,
+        })
+        .into());
+    }
+    let tradeable_amount = size.quantity;
+
+    let tp_sl = resolve_tp_sl(venue_a, market_name_a, market_a, &pair.tp_sl).await?;
+    let a = LegCtx {
+        venue: venue_a,
+        leg: leg_a,
+        market: market_a,
+    };
+    let b = LegCtx {
+        venue: venue_b,
+        leg: leg_b,
+        market: market_b,
+    };
+
+    let entry = match pair.entry_mode {
+        EntryMode::Taker => place_taker_entry(a, b, side_a, tradeable_amount, tp_sl).await,
+        EntryMode::Twap => place_twap_entry(a, b, side_a, tradeable_amount, tp_sl, pair).await,
+        EntryMode::Maker => {
+            place_maker_entry(
+                a,
+                b,
+                side_a,
+                tradeable_amount,
+                tp_sl,
+                Duration::from_secs(pair.maker_timeout_secs),
+            )
+            .await
+        }
+    };
+    // A TWAP that stopped early still leaves the slices it sent to reconcile
+    let stopped_early = match entry {
+        Err(e) if matches!(e.downcast_ref::<BotError>(), Some(BotError::Skip(_))) => Some(e),
+        entry => {
+            entry?;
+            None
+        }
+    };
+
+    let reconciliation = reconcile_legs(a, b, side_a).await?;
+    if !reconciliation.balanced {
+        return Err(anyhow!(
+            "Legs are still unbalanced after {} adjustments: {} {} on {}, {} {} on {}",
+            reconciliation.adjustments,
+            reconciliation.quantity_a,
+            market_name_a,
+            venue_a.name(),
+            reconciliation.quantity_b,
+            market_name_b,
+            venue_b.name()
+        ));
+    }
+
+    stopped_early.map_or(Ok(()), Err)
+}
+
+/// Difference between two prices in percent of the higher one.
+pub fn price_spread_percent(price_a: f64, price_b: f64) -> f64 {
+    if price_a > price_b {
+        let price_diff = price_a - price_b;
+        price_diff / price_a * 100.0
+    } else {
+        let price_diff = price_b - price_a;
+        price_diff / price_b * 100.0
+    }
+}
+
+/// Opens both legs with market orders. Both orders are signed first and then
+/// sent at the same time, so neither leg waits on the other venue; a leg that
+/// went through while the other failed is unwound.
+pub async fn place_taker_entry<A: PerpVenue, B: PerpVenue>(
+    a: LegCtx<'_, A>,
+    b: LegCtx<'_, B>,
+    side_a: Side,
+    tradeable_amount: f64,
+    tp_sl: Option<TpSl>,
+) -> anyhow::Result<()> {
+    let LegCtx {
+        venue: venue_a,
+        leg: leg_a,
+        market: market_a,
+    } = a;
+    let LegCtx {
+        venue: venue_b,
+        leg: leg_b,
+        market: market_b,
+    } = b;
+    let market_name_a = leg_a.market.as_str();
+    let market_name_b = leg_b.market.as_str();
+
+    // Positions before the entry, so only what this entry filled is unwound
+    let (order_a, order_b, size_before_a, size_before_b) = tokio::try_join!(
+        venue_a.prepare_order(
+            market_name_a,
+            market_a,
+            side_a,
+            tradeable_amount,
+            leg_a.slippage,
+            OrderIntent::Open(tp_sl),
+        ),
+        venue_b.prepare_order(
+            market_name_b,
+            market_b,
+            side_a.opposite(),
+            tradeable_amount,
+            leg_b.slippage,
+            OrderIntent::Open(tp_sl),
+        ),
+        position_size(venue_a, market_name_a),
+        position_size(venue_b, market_name_b),
+    )?;
+
+    let (result_a, result_b) =
+        tokio::join!(venue_a.submit_order(order_a), venue_b.submit_order(order_b));
+
+    match (result_a, result_b) {
+        (Ok(ack_a), Ok(ack_b)) => {
+            log_order_ack(venue_a.name(), market_name_a, &ack_a);
+            log_order_ack(venue_b.name(), market_name_b, &ack_b);
+            Ok(())
+        }
+        (Ok(ack_a), Err(e)) => {
+            log_order_ack(venue_a.name(), market_name_a, &ack_a);
+            let unwind = unwind_leg(a, size_before_a).await;
+            Err(unwind_error(e, venue_b.name(), venue_a.name(), unwind))
+        }
+        (Err(e), Ok(ack_b)) => {
+            log_order_ack(venue_b.name(), market_name_b, &ack_b);
+            let unwind = unwind_leg(b, size_before_b).await;
+            Err(unwind_error(e, venue_a.name(), venue_b.name(), unwind))
+        }
+        (Err(e_a), Err(e_b)) => Err(e_a.context(format!(
+            "Both legs failed, nothing to unwind ({} error: {:#})",
+            venue_b.name(),
+            e_b
+        ))),
+    }
+}
+
+/// Error of an entry whose order on `failed_venue` failed, keeping the result
+/// of unwinding the leg that went through on `unwound_venue`.
+fn unwind_error(
+    error: anyhow::Error,
+    failed_venue: &str,
+    unwound_venue: &str,
+    unwind: anyhow::Result<f64>,
+) -> anyhow::Error {
+    match unwind {
+        Ok(qty) => error.context(format!(
+            "Failed to place {} order, {} {} leg was unwound",
+            failed_venue, qty, unwound_venue
+        )),
+        Err(unwind_error) => error.context(format!(
+            "Failed to place {} order and to unwind the {} leg, which is left open (unwind error: {:#})",
+            failed_venue, unwound_venue, unwind_error
+        )),
+    }
+}
+
+pub fn log_order_ack(venue: &str, market_name: &str, ack: &OrderAck) {
+    println!(
+        "{} order for {} accepted: client id {}, venue id {}",
+        venue,
+        market_name,
+        ack.client_order_id,
+        ack.venue_order_id.as_deref().unwrap_or("unknown")
+    );
+}
@@ -0,0 +1,99 @@
+use crate::{
+    config::structs::{AllocationConfig, MarketPair},
+    portfolio::{check_limits::check_limits, structs::Portfolio},
+    sizing::{size_position::size_position, structs::SizeLimit},
+    strategy::structs::{Opportunity, PlannedEntry},
+};
+
+/// Ranks the pairs worth entering by net expected return and gives capital to
+/// the best `max_new_pairs` of them. Each pair is sized against the collateral
+/// and the allocation caps left after the better ones.
+pub fn plan_entries<'a, MA, MB>(
+    mut opportunities: Vec<(&'a MarketPair, Opportunity<MA, MB>)>,
+    portfolio: &Portfolio,
+    allocation: &AllocationConfig,
+) -> Vec<PlannedEntry<'a, MA, MB>> {
+    opportunities.sort_by(|(_, a), (_, b)| b.net_return.total_cmp(&a.net_return));
+
+    let mut portfolio = portfolio.clone();
+    let mut planned = Vec::new();
+    for (rank, (pair, opportunity)) in opportunities.into_iter().enumerate() {
+        let name = format!("{}/{}", pair.extended.market, pair.pacifica.market);
+
+        if planned.len() >= allocation.max_new_pairs {
+            println!(
+                "#{} {}: net return {}%, outside the best {} pairs",
+                rank + 1,
+                name,
+                opportunity.net_return,
+                allocation.max_new_pairs
+            );
+            continue;
+        }
+
+        let headroom =
+            match check_limits(&portfolio, pair, &pair.extended, &pair.pacifica, allocation) {
+                Ok(headroom) => headroom,
+                Err(e) => {
+                    println!(
+                        "#{} {}: net return {}%, {}",
+                        rank + 1,
+                        name,
+                        opportunity.net_return,
+                        e
+                    );
+                    continue;
+                }
+            };
+
+        let size = size_position(
+            pair,
+            &portfolio.balance_a,
+            &opportunity.snapshot_a,
+            &portfolio.balance_b,
+            &opportunity.snapshot_b,
+            opportunity.net_return,
+        )
+        .capped(headroom, SizeLimit::Allocation);
+
+        let lot_size = opportunity
+            .snapshot_a
+            .lot_size
+            .max(opportunity.snapshot_b.lot_size);
+        if size.quantity < lot_size {
+            println!(
+                "#{} {}: net return {}%, {} USD left by the {} is below one lot",
+                rank + 1,
+                name,
+                opportunity.net_return,
+                size.notional,
+                size.limited_by
+            );
+            continue;
+        }
+
+        println!(
+            "#{} {}: net return {}%, allocated {} USD",
+            rank + 1,
+            name,
+            opportunity.net_return,
+            size.notional
+        );
+
+        portfolio.add_pair(
+            &pair.extended,
+            &pair.pacifica,
+            opportunity.side_a,
+            opportunity.snapshot_a.ask.max(opportunity.snapshot_b.ask),
+            &size,
+            pair.sizing.leverage,
+        );
+        planned.push(PlannedEntry {
+            pair,
+            opportunity,
+            size,
+        });
+    }
+
+    planned
+}
@@ -0,0 +1,12 @@
+use crate::venue::perp_venue::PerpVenue;
+
+/// Signed size of the open position in `market_name`, zero when there is none.
+pub async fn position_size<V: PerpVenue>(venue: &V, market_name: &str) -> anyhow::Result<f64> {
+    Ok(venue
+        .get_open_positions()
+        .await?
+        .iter()
+        .find(|position| position.market == market_name)
+        .map(|position| position.size)
+        .unwrap_or(0.0))
+}
@@ -0,0 +1,45 @@
+use anyhow::Context;
+
+use crate::{
+    strategy::structs::LegCtx,
+    venue::{
+        perp_venue::PerpVenue,
+        structs::{Side, TpSl},
+    },
+};
+
+/// Places one TP/SL for the `qty` a leg opened with `side` in several orders
+/// sent without one. Does nothing without a `tp_sl` or a quantity.
+pub async fn protect_leg<V: PerpVenue>(
+    leg: LegCtx<'_, V>,
+    side: Side,
+    qty: f64,
+    tp_sl: Option<TpSl>,
+) -> anyhow::Result<()> {
+    let LegCtx { venue, leg, market } = leg;
+    let Some(tp_sl) = tp_sl else {
+        return Ok(());
+    };
+    if qty <= 0.0 {
+        return Ok(());
+    }
+
+    venue
+        .place_tp_sl(&leg.market, market, side, qty, tp_sl)
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to place TP/SL for {} on {}",
+                leg.market,
+                venue.name()
+            )
+        })?;
+    println!(
+        "TP/SL placed for {} {} on {}",
+        qty,
+        leg.market,
+        venue.name()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use anyhow::Context;
+use tokio::time::{Duration, sleep};
+
+use crate::{
+    strategy::structs::{LegCtx, LegReconciliation},
+    venue::{
+        perp_venue::PerpVenue,
+        structs::{OrderIntent, PositionSide, Side},
+    },
+};
+
+const MAX_RECONCILE_ATTEMPTS: u32 = 3;
+// Venues can take a moment to report a fill in the positions endpoint
+pub const FILL_SETTLE_DELAY_MILLIS: u64 = 500;
+
+/// Re-reads both positions after an entry and tops up or trims the short leg
+/// until the hedge is delta-neutral within one lot. `side_a` is the side the
+/// entry order on venue A was sent with. Top-ups carry no take profit or
+/// stop loss, so the leg keeps the single set placed with the entry.
+pub async fn reconcile_legs<A: PerpVenue, B: PerpVenue>(
+    a: LegCtx<'_, A>,
+    b: LegCtx<'_, B>,
+    side_a: Side,
+) -> anyhow::Result<LegReconciliation> {
+    let LegCtx {
+        venue: venue_a,
+        leg: leg_a,
+        market: market_a,
+    } = a;
+    let LegCtx {
+        venue: venue_b,
+        leg: leg_b,
+        market: market_b,
+    } = b;
+    let lot_size = venue_a
+        .market_snapshot(market_a)?
+        .lot_size
+        .max(venue_b.market_snapshot(market_b)?.lot_size);
+    let mut adjustments = 0;
+
+    loop {
+        sleep(Duration::from_millis(FILL_SETTLE_DELAY_MILLIS)).await;
+
+        let quantity_a = position_quantity(venue_a, &leg_a.market).await?;
+        let quantity_b = position_quantity(venue_b, &leg_b.market).await?;
+        let excess = match side_a {
+            // A is the short leg: positive when it is larger than the long leg
+            Side::Sell => quantity_a - quantity_b,
+            Side::Buy => quantity_b - quantity_a,
+        };
+
+        let reconciliation = LegReconciliation {
+            market_a: leg_a.market.to_string(),
+            market_b: leg_b.market.to_string(),
+            quantity_a,
+            quantity_b,
+            adjustments,
+            balanced: excess.abs() < lot_size,
+        };
+
+        if reconciliation.balanced || adjustments >= MAX_RECONCILE_ATTEMPTS {
+            println!(
+                "Legs of {} and {}: {} {}, {} {}, {} adjustment(s), balanced: {}",
+                reconciliation.market_a,
+                reconciliation.market_b,
+                venue_a.name(),
+                quantity_a,
+                venue_b.name(),
+                quantity_b,
+                adjustments,
+                reconciliation.balanced
+            );
+            return Ok(reconciliation);
+        }
+
+        // Trimming reduces the short leg, topping up opens more of it like the entry did
+        let (side, intent) = if excess > 0.0 {
+            (PositionSide::Short.closing_side(), OrderIntent::Reduce)
+        } else {
+            (PositionSide::Short.opening_side(), OrderIntent::Open(None))
+        };
+
+        match side_a {
+            Side::Sell => {
+                venue_a
+                    .place_order(
+                        &leg_a.market,
+                        market_a,
+                        side,
+                        excess.abs(),
+                        leg_a.slippage,
+                        intent,
+                    )
+                    .await?
+            }
+            Side::Buy => {
+                venue_b
+                    .place_order(
+                        &leg_b.market,
+                        market_b,
+                        side,
+                        excess.abs(),
+                        leg_b.slippage,
+                        intent,
+                    )
+                    .await?
+            }
+        };
+        adjustments += 1;
+    }
+}
+
+/// Unsigned size of the open position in `market_name`, zero when there is none.
+async fn position_quantity<V: PerpVenue>(venue: &V, market_name: &str) -> anyhow::Result<f64> {
+    let positions = venue
+        .get_open_positions()
+        .await
+        .with_context(|| format!("Failed to read {} positions after entry", venue.name()))?;
+
+    Ok(positions
+        .iter()
+        .find(|position| position.market == market_name)
+        .map(|position| position.quantity())
+        .unwrap_or(0.0))
+}
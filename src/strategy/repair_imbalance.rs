@@ -0,0 +1,128 @@
+use crate::{
+    config::structs::{ImbalanceRepair, PairLeg, RebalanceConfig},
+    strategy::structs::VenueLeg,
+    venue::{
+        perp_venue::PerpVenue,
+        structs::{OrderIntent, Position, Side},
+    },
+};
+
+/// Checks that the two legs of a pair still hedge each other and, when they
+/// drifted apart by more than the tolerance, sends one order to bring them back
+/// in line. `contract_ratio` is the number of venue A contracts one venue B
+/// contract is worth. Hedging orders carry no take profit or stop loss, so the
+/// leg keeps the single set placed with the entry. Returns whether an order
+/// was sent.
+pub async fn repair_imbalance<A: PerpVenue, B: PerpVenue>(
+    a: VenueLeg<'_, A>,
+    position_a: Option<&Position>,
+    b: VenueLeg<'_, B>,
+    position_b: Option<&Position>,
+    contract_ratio: f64,
+    rebalance: &RebalanceConfig,
+) -> anyhow::Result<bool> {
+    let VenueLeg {
+        venue: venue_a,
+        leg: leg_a,
+    } = a;
+    let VenueLeg {
+        venue: venue_b,
+        leg: leg_b,
+    } = b;
+    // A missing leg is left to the pair's orphan policy
+    let (Some(position_a), Some(position_b)) = (position_a, position_b) else {
+        return Ok(false);
+    };
+
+    // Both sizes in venue A contracts, signed so a perfect hedge sums to zero
+    let size_a = position_a.size;
+    let size_b = position_b.size * contract_ratio;
+    let net = size_a + size_b;
+    let larger = size_a.abs().max(size_b.abs());
+
+    if net.abs() <= larger * rebalance.tolerance {
+        return Ok(false);
+    }
+
+    println!(
+        "Hedge imbalance on {} / {}: {} {}, {} {} (net {})",
+        leg_a.market,
+        leg_b.market,
+        venue_a.name(),
+        size_a,
+        venue_b.name(),
+        size_b,
+        net
+    );
+
+    let a_is_larger = size_a.abs() >= size_b.abs();
+    // Selling brings a net long back to zero, buying a net short
+    let net_closing_side = if net > 0.0 { Side::Sell } else { Side::Buy };
+
+    match (rebalance.repair, a_is_larger) {
+        (ImbalanceRepair::Reduce, true) => {
+            let qty = net.abs().min(size_a.abs());
+            send_repair_order(venue_a, leg_a, net_closing_side, qty, OrderIntent::Reduce).await
+        }
+        (ImbalanceRepair::Reduce, false) => {
+            let qty = net.abs().min(size_b.abs()) / contract_ratio;
+            send_repair_order(venue_b, leg_b, net_closing_side, qty, OrderIntent::Reduce).await
+        }
+        (ImbalanceRepair::Hedge, true) => {
+            let qty = net.abs() / contract_ratio;
+            send_repair_order(
+                venue_b,
+                leg_b,
+                net_closing_side,
+                qty,
+                OrderIntent::Open(None),
+            )
+            .await
+        }
+        (ImbalanceRepair::Hedge, false) => {
+            send_repair_order(
+                venue_a,
+                leg_a,
+                net_closing_side,
+                net.abs(),
+                OrderIntent::Open(None),
+            )
+            .await
+        }
+    }
+}
+
+/// Sends a repair order unless it is smaller than one lot.
+async fn send_repair_order<V: PerpVenue>(
+    venue: &V,
+    leg: &PairLeg,
+    side: Side,
+    qty: f64,
+    intent: OrderIntent,
+) -> anyhow::Result<bool> {
+    let market = venue.get_market_data(&leg.market).await?;
+    let lot_size = venue.market_snapshot(&market)?.lot_size;
+
+    if qty < lot_size {
+        println!(
+            "Imbalance of {} {} on {} is below one lot, leaving it",
+            qty,
+            leg.market,
+            venue.name()
+        );
+        return Ok(false);
+    }
+
+    println!(
+        "Repairing hedge: {:?} {} {} on {}",
+        side,
+        qty,
+        leg.market,
+        venue.name()
+    );
+    venue
+        .place_order(&leg.market, &market, side, qty, leg.slippage, intent)
+        .await?;
+
+    Ok(true)
+}
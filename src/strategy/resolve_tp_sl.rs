@@ -0,0 +1,67 @@
+use anyhow::anyhow;
+
+use crate::{
+    config::structs::{TpSlMode, TpSlPolicy},
+    utils::utils::average_true_range,
+    venue::{perp_venue::PerpVenue, structs::TpSl},
+};
+
+/// Turns a pair's TP/SL policy into the distances attached to its opening
+/// orders, `None` when the pair trades without them. ATR based distances are
+/// measured on the hourly candles of `venue`.
+pub async fn resolve_tp_sl<V: PerpVenue>(
+    venue: &V,
+    market_name: &str,
+    market: &V::MarketData,
+    policy: &TpSlPolicy,
+) -> anyhow::Result<Option<TpSl>> {
+    if !policy.enabled {
+        return Ok(None);
+    }
+
+    let (take_profit, stop_loss) = match policy.mode {
+        TpSlMode::Percent => (policy.take_profit, policy.stop_loss),
+        TpSlMode::Atr => {
+            // One extra candle gives the first true range its previous close
+            let candles = venue
+                .get_candles(market_name, policy.atr_period + 1)
+                .await?;
+            let atr = average_true_range(&candles, policy.atr_period).ok_or_else(|| {
+                anyhow!(
+                    "Only {} candles for {} on {}, {} needed for the ATR",
+                    candles.len(),
+                    market_name,
+                    venue.name(),
+                    policy.atr_period + 1
+                )
+            })?;
+            let price = venue.market_snapshot(market)?.mark;
+            println!(
+                "ATR of {} on {}: {} ({}% of the price)",
+                market_name,
+                venue.name(),
+                atr,
+                atr / price * 100.0
+            );
+
+            (
+                policy.take_profit * atr / price,
+                policy.stop_loss * atr / price,
+            )
+        }
+    };
+
+    if stop_loss + policy.limit_offset >= 1.0 {
+        return Err(anyhow!(
+            "Stop loss of {} for {} would be at or below a zero price",
+            stop_loss,
+            market_name
+        ));
+    }
+
+    Ok(Some(TpSl {
+        take_profit,
+        stop_loss,
+        limit_offset: policy.limit_offset,
+    }))
+}
@@ -0,0 +1,131 @@
+use std::fmt;
+
+use crate::{
+    config::structs::{MarketPair, PairLeg},
+    sizing::structs::PositionSize,
+    venue::{
+        perp_venue::PerpVenue,
+        structs::{MarketSnapshot, Side},
+    },
+};
+
+/// One leg of a pair: the venue it trades on and its config.
+pub struct VenueLeg<'a, V: PerpVenue> {
+    pub venue: &'a V,
+    pub leg: &'a PairLeg,
+}
+
+// Derives would require the venue itself to be `Copy`
+impl<V: PerpVenue> Clone for VenueLeg<'_, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V: PerpVenue> Copy for VenueLeg<'_, V> {}
+
+/// One leg of an entry: the venue, its config and the market data the entry
+/// was decided on.
+pub struct LegCtx<'a, V: PerpVenue> {
+    pub venue: &'a V,
+    pub leg: &'a PairLeg,
+    pub market: &'a V::MarketData,
+}
+
+impl<V: PerpVenue> Clone for LegCtx<'_, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V: PerpVenue> Copy for LegCtx<'_, V> {}
+
+/// Filled quantities of both legs of a hedge after reconciliation.
+#[derive(Debug, Clone)]
+pub struct LegReconciliation {
+    pub market_a: String,
+    pub market_b: String,
+    /// Unsigned position sizes in base units.
+    pub quantity_a: f64,
+    pub quantity_b: f64,
+    /// Number of orders sent to bring the legs back in line.
+    pub adjustments: u32,
+    /// Whether the legs match within one lot.
+    pub balanced: bool,
+}
+
+/// Expected profit of a hedged pair over its holding period, in USD.
+#[derive(Debug, Clone, Copy)]
+pub struct EdgeEstimate {
+    /// Funding earned from the rate difference.
+    pub funding: f64,
+    /// Entry and exit fees on both venues.
+    pub fees: f64,
+    /// Estimated price impact of the four fills.
+    pub slippage: f64,
+    /// Gain from selling above the buy price at entry, negative when paying up.
+    pub basis: f64,
+    pub net: f64,
+}
+
+/// Exit rule that closed an open pair.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExitReason {
+    /// Funding rate difference in favor of the pair, in percent per hour, fell
+    /// below the exit threshold.
+    FundingBelowExit {
+        diff: f64,
+        threshold: f64,
+    },
+    /// Price profit of both legs, in percent of the notional, reached the target.
+    BasisTakeProfit {
+        pnl: f64,
+        target: f64,
+    },
+    MaxHoldingTime {
+        hours: f64,
+        limit: f64,
+    },
+}
+
+impl fmt::Display for ExitReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExitReason::FundingBelowExit { diff, threshold } => write!(
+                f,
+                "funding rate diff {}%/h is below the exit threshold {}%/h",
+                diff, threshold
+            ),
+            ExitReason::BasisTakeProfit { pnl, target } => write!(
+                f,
+                "basis profit {}% reached the take profit {}%",
+                pnl, target
+            ),
+            ExitReason::MaxHoldingTime { hours, limit } => {
+                write!(f, "held for {}h, longer than the maximum {}h", hours, limit)
+            }
+        }
+    }
+}
+
+/// A pair that passed the entry checks, with the market data needed to open it.
+#[derive(Debug)]
+pub struct Opportunity<MA, MB> {
+    pub market_a: MA,
+    pub market_b: MB,
+    pub snapshot_a: MarketSnapshot,
+    pub snapshot_b: MarketSnapshot,
+    /// Side of the leg on venue A, venue B takes the other one.
+    pub side_a: Side,
+    pub edge: EdgeEstimate,
+    /// Net expected profit in percent of the notional, used to rank pairs.
+    pub net_return: f64,
+}
+
+/// A pair the cycle planner decided to open, with the capital given to it.
+#[derive(Debug)]
+pub struct PlannedEntry<'a, MA, MB> {
+    pub pair: &'a MarketPair,
+    pub opportunity: Opportunity<MA, MB>,
+    pub size: PositionSize,
+}
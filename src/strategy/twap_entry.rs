@@ -0,0 +1,132 @@
+use anyhow::anyhow;
+use tokio::time::{Duration, sleep};
+
+use crate::{
+    config::structs::MarketPair,
+    error::bot_error::{BotError, SkipReason},
+    strategy::{
+        combine_errors::combine_errors,
+        place_arb_order::{place_taker_entry, price_spread_percent},
+        protect_leg::protect_leg,
+        structs::LegCtx,
+    },
+    venue::{
+        perp_venue::PerpVenue,
+        structs::{Side, TpSl},
+    },
+};
+
+/// Opens a pair in `pair.twap_slices` equal child pairs spread over
+/// `pair.twap_window_secs`. Each child is hedged before the next one is sent,
+/// and the execution stops early with a `SkipReason::TwapAborted` when the
+/// price spread between the venues moves past the pair's threshold. Children
+/// are sent without TP/SL, each leg gets a single set for what was sent once
+/// the execution ends. Slices are split by time only, the order books are not
+/// read.
+pub async fn place_twap_entry<A: PerpVenue, B: PerpVenue>(
+    a: LegCtx<'_, A>,
+    b: LegCtx<'_, B>,
+    side_a: Side,
+    qty: f64,
+    tp_sl: Option<TpSl>,
+    pair: &MarketPair,
+) -> anyhow::Result<()> {
+    let LegCtx {
+        venue: venue_a,
+        leg: leg_a,
+        market: market_a,
+    } = a;
+    let LegCtx {
+        venue: venue_b,
+        leg: leg_b,
+        market: market_b,
+    } = b;
+    let lot_size = venue_a
+        .market_snapshot(market_a)?
+        .lot_size
+        .max(venue_b.market_snapshot(market_b)?.lot_size);
+    // Every child must still be at least one lot on both venues
+    let slices = (pair.twap_slices as f64)
+        .min((qty / lot_size).floor())
+        .max(1.0) as u32;
+    // Whole lots of the coarser venue, so both legs of a child fill the same size
+    let child_qty = ((qty / slices as f64 / lot_size) + 1e-9).floor() * lot_size;
+    if child_qty < lot_size {
+        return Err(anyhow!(
+            "TWAP size {} on {} is smaller than one lot",
+            qty,
+            leg_a.market
+        ));
+    }
+    let interval = Duration::from_secs(pair.twap_window_secs) / slices;
+
+    let mut sent = 0;
+    let sliced = async {
+        for slice in 0..slices {
+            // The first child uses the market data the entry was decided on
+            let (child_market_a, child_market_b) = if slice == 0 {
+                (None, None)
+            } else {
+                sleep(interval).await;
+                (
+                    Some(venue_a.get_market_data(&leg_a.market).await?),
+                    Some(venue_b.get_market_data(&leg_b.market).await?),
+                )
+            };
+            let child_market_a = child_market_a.as_ref().unwrap_or(market_a);
+            let child_market_b = child_market_b.as_ref().unwrap_or(market_b);
+
+            let price_spread = price_spread_percent(
+                venue_a.market_snapshot(child_market_a)?.bid,
+                venue_b.market_snapshot(child_market_b)?.bid,
+            );
+            if price_spread > pair.price_spread_threshold {
+                return Ok(Some(SkipReason::TwapAborted {
+                    sent,
+                    slices,
+                    spread: price_spread,
+                    threshold: pair.price_spread_threshold,
+                }));
+            }
+
+            place_taker_entry(
+                LegCtx {
+                    market: child_market_a,
+                    ..a
+                },
+                LegCtx {
+                    market: child_market_b,
+                    ..b
+                },
+                side_a,
+                child_qty,
+                None,
+            )
+            .await?;
+            sent += 1;
+            println!(
+                "TWAP on {}: slice {} of {} sent ({} each)",
+                leg_a.market, sent, slices, child_qty
+            );
+        }
+        anyhow::Ok(None)
+    }
+    .await;
+
+    // Protects what was sent even when the execution stopped early
+    let opened = sent as f64 * child_qty;
+    let protected_a = protect_leg(a, side_a, opened, tp_sl).await;
+    let protected_b = protect_leg(b, side_a.opposite(), opened, tp_sl).await;
+    let sliced = match sliced {
+        Ok(Some(reason)) => Err(BotError::Skip(reason).into()),
+        Ok(None) => Ok(()),
+        Err(e) => Err(e),
+    };
+
+    combine_errors(
+        [sliced.err(), protected_a.err(), protected_b.err()]
+            .into_iter()
+            .flatten()
+            .collect(),
+    )
+}
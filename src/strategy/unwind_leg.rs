@@ -0,0 +1,46 @@
+use tokio::time::{Duration, sleep};
+
+use crate::{
+    strategy::{
+        place_arb_order::log_order_ack, position_size::position_size,
+        reconcile_legs::FILL_SETTLE_DELAY_MILLIS, structs::LegCtx,
+    },
+    venue::{
+        perp_venue::PerpVenue,
+        structs::{OrderIntent, Side},
+    },
+};
+
+/// Closes what an entry order filled on a leg whose counterpart failed.
+/// `size_before` is the signed position size before the entry was sent, so
+/// only the fill of this entry is reduced. Returns the quantity unwound.
+pub async fn unwind_leg<V: PerpVenue>(leg: LegCtx<'_, V>, size_before: f64) -> anyhow::Result<f64> {
+    let LegCtx { venue, leg, market } = leg;
+    sleep(Duration::from_millis(FILL_SETTLE_DELAY_MILLIS)).await;
+
+    let filled = position_size(venue, &leg.market).await? - size_before;
+    if filled.abs() < venue.market_snapshot(market)?.lot_size {
+        println!(
+            "Nothing to unwind on {} for {}, filled {}",
+            venue.name(),
+            leg.market,
+            filled
+        );
+        return Ok(0.0);
+    }
+
+    let side = if filled > 0.0 { Side::Sell } else { Side::Buy };
+    let ack = venue
+        .place_order(
+            &leg.market,
+            market,
+            side,
+            filled.abs(),
+            leg.slippage,
+            OrderIntent::Reduce,
+        )
+        .await?;
+    log_order_ack(venue.name(), &leg.market, &ack);
+
+    Ok(filled.abs())
+}
@@ -1 +1,2 @@
 pub mod utils;
+pub mod venue_client;
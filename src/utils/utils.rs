@@ -1,3 +1,7 @@
+use rust_decimal::Decimal;
+
+use crate::venue::structs::Candle;
+
 #[derive(Debug, Clone, Copy)]
 pub enum RoundingMode {
     Floor,
@@ -5,11 +9,11 @@ pub enum RoundingMode {
     Round,
 }
 
-pub fn round_to_min_change_f64(
-    value: f64,
-    min_change: f64,
+pub fn round_to_min_change(
+    value: Decimal,
+    min_change: Decimal,
     rounding_mode: Option<RoundingMode>,
-) -> f64 {
+) -> Decimal {
     let mode = rounding_mode.unwrap_or(RoundingMode::Round);
 
     // Divide value by min_change
@@ -22,45 +26,31 @@ pub fn round_to_min_change_f64(
         RoundingMode::Round => divided.round(),
     };
 
-    // Multiply back by min_change
-    let result = rounded * min_change;
-
-    // Get decimal places of min_change
-    let decimal_places = get_decimal_places(&min_change);
-
-    // Round to the same decimal places as min_change
-    round_to_decimal_places(result, decimal_places)
+    // Multiply back by min_change, keeping only as many decimal places as min_change has
+    (rounded * min_change)
+        .round_dp(get_decimal_places(&min_change))
+        .normalize()
 }
 
-fn get_decimal_places(value: &f64) -> u32 {
-    let s = format!("{:.10}", value);
-    let parts: Vec<&str> = s.split('.').collect();
-
-    if parts.len() == 2 {
-        // Count trailing zeros and remove them to get actual decimal places
-        parts[1].trim_end_matches('0').len() as u32
-    } else {
-        0
-    }
-}
-
-fn round_to_decimal_places(value: f64, decimal_places: u32) -> f64 {
-    let multiplier = 10_f64.powi(decimal_places as i32);
-    (value * multiplier).round() / multiplier
+fn get_decimal_places(value: &Decimal) -> u32 {
+    // Trailing zeros do not count, "0.0010" has three decimal places
+    value.normalize().scale()
 }
 
-pub fn calc_entire_position_size(
-    price: &f64,
-    min_order_size_change: &f64,
-    max_position_value: &f64,
-) -> f64 {
-    // Calculate: maxPositionValue * 50 / price
-    let result = max_position_value * 50.0 / price;
+/// Average true range over the last `period` candles, `None` when there are
+/// not enough of them. `candles` must be ordered oldest first.
+pub fn average_true_range(candles: &[Candle], period: usize) -> Option<f64> {
+    if period == 0 || candles.len() < period + 1 {
+        return None;
+    }
 
-    // Get decimal places of min_order_size_change
-    let decimal_places = get_decimal_places(min_order_size_change);
+    let true_ranges = candles.windows(2).map(|pair| {
+        let (previous, candle) = (&pair[0], &pair[1]);
+        (candle.high - candle.low)
+            .max((candle.high - previous.close).abs())
+            .max((candle.low - previous.close).abs())
+    });
+    let recent = true_ranges.skip(candles.len() - 1 - period);
 
-    // Round down to the same decimal places as min_order_size_change
-    let multiplier = 10_f64.powi(decimal_places as i32);
-    (result * multiplier).floor() / multiplier
+    Some(recent.sum::<f64>() / period as f64)
 }
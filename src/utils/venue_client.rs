@@ -0,0 +1,69 @@
+use std::time::Duration;
+
+use reqwest::{
+    Client, RequestBuilder,
+    header::{HeaderMap, HeaderValue, USER_AGENT},
+};
+use serde::de::DeserializeOwned;
+
+use crate::{config::structs::VenueHttpConfig, error::bot_error::BotError};
+
+const BROWSER_USER_AGENT: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+const POOL_IDLE_TIMEOUT_SECONDS: u64 = 90;
+
+/// HTTP client shared by every request sent to one venue. Connections are
+/// pooled and the browser-like headers the venues expect are set once here.
+#[derive(Clone, Debug)]
+pub struct VenueClient {
+    pub http: Client,
+    pub base_url: String,
+}
+
+impl VenueClient {
+    pub fn new(config: &VenueHttpConfig) -> anyhow::Result<VenueClient> {
+        let mut headers = HeaderMap::new();
+        headers.insert(USER_AGENT, HeaderValue::from_static(BROWSER_USER_AGENT));
+
+        let http = Client::builder()
+            .default_headers(headers)
+            .timeout(Duration::from_secs(config.request_timeout_secs))
+            .connect_timeout(Duration::from_secs(config.connect_timeout_secs))
+            .pool_idle_timeout(Duration::from_secs(POOL_IDLE_TIMEOUT_SECONDS))
+            .build()?;
+
+        Ok(VenueClient {
+            http,
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// Full URL of an endpoint given its path, e.g. `/api/v1/info`.
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    /// Sends a request and returns the response body, turning network
+    /// failures and non-success statuses into typed errors.
+    pub async fn send_text(&self, request: RequestBuilder) -> Result<String, BotError> {
+        let response = request.send().await?;
+        let status = response.status();
+        let body = response.text().await?;
+
+        if !status.is_success() {
+            return Err(BotError::from_response(status.as_u16(), body));
+        }
+
+        Ok(body)
+    }
+
+    /// Like `send_text`, then decodes the body as JSON.
+    pub async fn send_json<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<T, BotError> {
+        let body = self.send_text(request).await?;
+
+        serde_json::from_str::<T>(&body)
+            .map_err(|e| BotError::parse(std::any::type_name::<T>(), format!("{}: {}", e, body)))
+    }
+}
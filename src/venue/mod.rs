@@ -0,0 +1,3 @@
+pub mod paper_venue;
+pub mod perp_venue;
+pub mod structs;
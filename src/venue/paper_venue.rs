@@ -0,0 +1,384 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::venue::{
+    perp_venue::PerpVenue,
+    structs::{
+        Balance, Candle, FeeRates, MarketSnapshot, OrderAck, OrderIntent, OrderStatus, Position,
+        PositionSide, Side, TpSl,
+    },
+};
+
+/// Wraps a venue for dry runs: market data comes from the live venue, orders
+/// are never sent and market orders fill immediately against the snapshot
+/// price instead. Limit orders rest until the book crosses their price. The
+/// simulated positions and balance are what the strategy sees, so the whole
+/// cycle behaves as it would live.
+pub struct PaperVenue<V: PerpVenue> {
+    pub inner: V,
+    /// Leverage used to work out the margin locked by simulated positions.
+    pub leverage: f64,
+    /// Served instead of the account's rates, which need credentials, and
+    /// charged on every simulated fill.
+    pub fees: FeeRates,
+    state: Mutex<PaperState>,
+}
+
+struct PaperState {
+    /// Starting balance plus realized PnL, less fees paid.
+    balance: f64,
+    positions: HashMap<String, Position>,
+    /// Limit orders not filled yet, by order id.
+    resting: HashMap<String, RestingOrder>,
+}
+
+#[derive(Clone)]
+struct RestingOrder {
+    market_name: String,
+    side: Side,
+    qty: f64,
+    price: f64,
+}
+
+impl PaperState {
+    /// Books a fill and charges `fee_rate` on its notional.
+    fn fill(
+        &mut self,
+        venue_name: &str,
+        market_name: &str,
+        side: Side,
+        qty: f64,
+        price: f64,
+        fee_rate: f64,
+    ) {
+        let realized_pnl = apply_fill(&mut self.positions, market_name, side, qty, price);
+        let fee = qty * price * fee_rate;
+        self.balance += realized_pnl - fee;
+
+        println!(
+            "[DRY RUN] {} {:?} {} {} filled at {} (realized PnL {}, fee {})",
+            venue_name, side, qty, market_name, price, realized_pnl, fee
+        );
+    }
+}
+
+impl<V: PerpVenue> PaperVenue<V> {
+    pub fn new(inner: V, initial_balance: f64, leverage: f64, fees: FeeRates) -> PaperVenue<V> {
+        PaperVenue {
+            inner,
+            leverage,
+            fees,
+            state: Mutex::new(PaperState {
+                balance: initial_balance,
+                positions: HashMap::new(),
+                resting: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Fills the resting limit orders the book has crossed since they were
+    /// placed, at their limit price and the maker fee.
+    async fn fill_resting_orders(&self) -> anyhow::Result<()> {
+        let resting = self.state.lock().unwrap().resting.clone();
+
+        for (order_id, order) in resting {
+            let market = self.inner.get_market_data(&order.market_name).await?;
+            let snapshot = self.inner.market_snapshot(&market)?;
+            if !crosses(order.side, order.price, &snapshot) {
+                continue;
+            }
+
+            let mut state = self.state.lock().unwrap();
+            // Skips orders cancelled while the book was being fetched
+            if state.resting.remove(&order_id).is_some() {
+                state.fill(
+                    self.inner.name(),
+                    &order.market_name,
+                    order.side,
+                    order.qty,
+                    order.price,
+                    self.fees.maker,
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Simulated market order, filled at the price seen when it was prepared.
+pub struct PaperOrder {
+    market_name: String,
+    side: Side,
+    qty: f64,
+    fill_price: f64,
+}
+
+#[async_trait]
+impl<V: PerpVenue> PerpVenue for PaperVenue<V> {
+    type MarketData = V::MarketData;
+    type PreparedOrder = PaperOrder;
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn get_market_data(&self, market_name: &str) -> anyhow::Result<V::MarketData> {
+        self.inner.get_market_data(market_name).await
+    }
+
+    fn market_snapshot(&self, market: &V::MarketData) -> anyhow::Result<MarketSnapshot> {
+        self.inner.market_snapshot(market)
+    }
+
+    async fn get_open_positions(&self) -> anyhow::Result<Vec<Position>> {
+        self.fill_resting_orders().await?;
+        let state = self.state.lock().unwrap();
+        Ok(state.positions.values().cloned().collect())
+    }
+
+    async fn get_tradeable_balance(&self) -> anyhow::Result<Balance> {
+        let state = self.state.lock().unwrap();
+        let used_margin = state
+            .positions
+            .values()
+            .map(|p| p.quantity() * p.entry_price / self.leverage)
+            .sum::<f64>();
+
+        Ok(Balance {
+            balance: state.balance,
+            available_for_trade: state.balance - used_margin,
+        })
+    }
+
+    async fn get_fees(&self, _market_name: &str) -> anyhow::Result<FeeRates> {
+        Ok(self.fees)
+    }
+
+    async fn get_candles(&self, market_name: &str, limit: usize) -> anyhow::Result<Vec<Candle>> {
+        self.inner.get_candles(market_name, limit).await
+    }
+
+    async fn prepare_order(
+        &self,
+        market_name: &str,
+        market: &V::MarketData,
+        side: Side,
+        qty: f64,
+        slippage: f64,
+        _intent: OrderIntent,
+    ) -> anyhow::Result<PaperOrder> {
+        let snapshot = self.inner.market_snapshot(market)?;
+        let fill_price = match side {
+            Side::Buy => snapshot.ask * (1.0 + slippage),
+            Side::Sell => snapshot.bid * (1.0 - slippage),
+        };
+
+        Ok(PaperOrder {
+            market_name: market_name.to_string(),
+            side,
+            qty,
+            fill_price,
+        })
+    }
+
+    async fn submit_order(&self, order: PaperOrder) -> anyhow::Result<OrderAck> {
+        self.state.lock().unwrap().fill(
+            self.inner.name(),
+            &order.market_name,
+            order.side,
+            order.qty,
+            order.fill_price,
+            self.fees.taker,
+        );
+
+        Ok(OrderAck {
+            venue_order_id: None,
+            client_order_id: uuid::Uuid::new_v4().to_string(),
+            status: OrderStatus::Accepted,
+            rejection_reason: None,
+            body: String::new(),
+        })
+    }
+
+    async fn place_limit_order(
+        &self,
+        market_name: &str,
+        market: &V::MarketData,
+        side: Side,
+        qty: f64,
+        price: f64,
+        _tp_sl: Option<TpSl>,
+    ) -> anyhow::Result<OrderAck> {
+        let snapshot = self.inner.market_snapshot(market)?;
+        let order_id = uuid::Uuid::new_v4().to_string();
+        let mut state = self.state.lock().unwrap();
+
+        if crosses(side, price, &snapshot) {
+            // A marketable limit order takes the touch
+            let fill_price = match side {
+                Side::Buy => snapshot.ask,
+                Side::Sell => snapshot.bid,
+            };
+            state.fill(
+                self.inner.name(),
+                market_name,
+                side,
+                qty,
+                fill_price,
+                self.fees.taker,
+            );
+        } else {
+            println!(
+                "[DRY RUN] {} limit {:?} {} {} resting at {}",
+                self.inner.name(),
+                side,
+                qty,
+                market_name,
+                price
+            );
+            state.resting.insert(
+                order_id.clone(),
+                RestingOrder {
+                    market_name: market_name.to_string(),
+                    side,
+                    qty,
+                    price,
+                },
+            );
+        }
+
+        Ok(OrderAck {
+            venue_order_id: Some(order_id.clone()),
+            client_order_id: order_id,
+            status: OrderStatus::Accepted,
+            rejection_reason: None,
+            body: String::new(),
+        })
+    }
+
+    async fn place_tp_sl(
+        &self,
+        market_name: &str,
+        _market: &V::MarketData,
+        side: Side,
+        qty: f64,
+        _tp_sl: TpSl,
+    ) -> anyhow::Result<()> {
+        // Simulated positions are never stopped out, the orders are only logged
+        println!(
+            "[DRY RUN] {} TP/SL for {:?} {} {}",
+            self.inner.name(),
+            side,
+            qty,
+            market_name
+        );
+        Ok(())
+    }
+
+    async fn cancel_order(&self, market_name: &str, order_id: &str) -> anyhow::Result<()> {
+        self.state.lock().unwrap().resting.remove(order_id);
+        println!(
+            "[DRY RUN] {} cancel {} on {}",
+            self.inner.name(),
+            order_id,
+            market_name
+        );
+        Ok(())
+    }
+
+    async fn cancel_all_orders(&self, market_name: &str) -> anyhow::Result<()> {
+        self.state
+            .lock()
+            .unwrap()
+            .resting
+            .retain(|_, order| order.market_name != market_name);
+        println!(
+            "[DRY RUN] {} cancel all orders on {}",
+            self.inner.name(),
+            market_name
+        );
+        Ok(())
+    }
+}
+
+/// Whether a limit order at `price` is marketable against the book.
+fn crosses(side: Side, price: f64, snapshot: &MarketSnapshot) -> bool {
+    match side {
+        Side::Buy => snapshot.ask <= price,
+        Side::Sell => snapshot.bid >= price,
+    }
+}
+
+/// Applies a fill to the simulated positions and returns the PnL it realized.
+/// Positions keep the time they were first opened until they flip side.
+fn apply_fill(
+    positions: &mut HashMap<String, Position>,
+    market_name: &str,
+    side: Side,
+    qty: f64,
+    fill_price: f64,
+) -> f64 {
+    let signed_qty = match side {
+        Side::Buy => qty,
+        Side::Sell => -qty,
+    };
+
+    let now_millis = Utc::now().timestamp_millis() as u64;
+
+    let Some(position) = positions.get(market_name) else {
+        positions.insert(
+            market_name.to_string(),
+            new_position(market_name, signed_qty, fill_price, now_millis),
+        );
+        return 0.0;
+    };
+
+    let old_size = position.size;
+    let new_size = old_size + signed_qty;
+
+    if old_size.signum() == signed_qty.signum() {
+        // Adding to the position moves the entry to the weighted average
+        let entry_price =
+            (old_size.abs() * position.entry_price + qty * fill_price) / new_size.abs();
+        positions.insert(
+            market_name.to_string(),
+            new_position(market_name, new_size, entry_price, position.opened_at),
+        );
+        return 0.0;
+    }
+
+    let closed_qty = qty.min(old_size.abs());
+    let realized_pnl = (fill_price - position.entry_price) * closed_qty * old_size.signum();
+    let entry_price = position.entry_price;
+    let opened_at = position.opened_at;
+
+    if new_size.abs() < f64::EPSILON {
+        positions.remove(market_name);
+    } else if new_size.signum() == old_size.signum() {
+        positions.insert(
+            market_name.to_string(),
+            new_position(market_name, new_size, entry_price, opened_at),
+        );
+    } else {
+        // The fill closed the position and opened one on the other side
+        positions.insert(
+            market_name.to_string(),
+            new_position(market_name, new_size, fill_price, now_millis),
+        );
+    }
+
+    realized_pnl
+}
+
+fn new_position(market_name: &str, size: f64, entry_price: f64, opened_at: u64) -> Position {
+    let side = if size > 0.0 {
+        PositionSide::Long
+    } else {
+        PositionSide::Short
+    };
+
+    Position::new(market_name.to_string(), side, size, entry_price, opened_at)
+}
@@ -0,0 +1,96 @@
+use async_trait::async_trait;
+
+use crate::venue::structs::{
+    Balance, Candle, FeeRates, MarketSnapshot, OrderAck, OrderIntent, Position, Side, TpSl,
+};
+
+/// Common interface over a perpetuals exchange so the strategy can run on any
+/// pair of venues.
+#[async_trait]
+pub trait PerpVenue: Send + Sync {
+    /// Venue specific market data, needed again when placing an order.
+    type MarketData: Send + Sync;
+    /// Signed order that is ready to be sent.
+    type PreparedOrder: Send;
+
+    fn name(&self) -> &str;
+
+    async fn get_market_data(&self, market_name: &str) -> anyhow::Result<Self::MarketData>;
+
+    /// Venue neutral view of the market data returned by `get_market_data`.
+    fn market_snapshot(&self, market: &Self::MarketData) -> anyhow::Result<MarketSnapshot>;
+
+    async fn get_open_positions(&self) -> anyhow::Result<Vec<Position>>;
+
+    async fn get_tradeable_balance(&self) -> anyhow::Result<Balance>;
+
+    /// Fees the account pays on the market.
+    async fn get_fees(&self, market_name: &str) -> anyhow::Result<FeeRates>;
+
+    /// The last `limit` hourly candles of the market, oldest first.
+    async fn get_candles(&self, market_name: &str, limit: usize) -> anyhow::Result<Vec<Candle>>;
+
+    /// Builds and signs a market order without sending it. `slippage` is the
+    /// maximum accepted price deviation as a fraction, opening orders carry the
+    /// take profit and stop loss of `intent`.
+    async fn prepare_order(
+        &self,
+        market_name: &str,
+        market: &Self::MarketData,
+        side: Side,
+        qty: f64,
+        slippage: f64,
+        intent: OrderIntent,
+    ) -> anyhow::Result<Self::PreparedOrder>;
+
+    /// Sends an order built by `prepare_order`. Rejected orders are returned
+    /// as errors.
+    async fn submit_order(&self, order: Self::PreparedOrder) -> anyhow::Result<OrderAck>;
+
+    /// Prepares and sends a market order in one go.
+    async fn place_order(
+        &self,
+        market_name: &str,
+        market: &Self::MarketData,
+        side: Side,
+        qty: f64,
+        slippage: f64,
+        intent: OrderIntent,
+    ) -> anyhow::Result<OrderAck> {
+        let order = self
+            .prepare_order(market_name, market, side, qty, slippage, intent)
+            .await?;
+        self.submit_order(order).await
+    }
+
+    /// Rests a post-only limit order at `price`. It is rejected instead of
+    /// crossing the book, so it only ever pays the maker fee. `tp_sl` is
+    /// attached the same way as on an opening market order.
+    async fn place_limit_order(
+        &self,
+        market_name: &str,
+        market: &Self::MarketData,
+        side: Side,
+        qty: f64,
+        price: f64,
+        tp_sl: Option<TpSl>,
+    ) -> anyhow::Result<OrderAck>;
+
+    /// Places a reduce-only take profit and stop loss closing `qty` of a
+    /// position opened with `side`, measured from the mid price like the ones
+    /// attached to opening orders. Protects a position built from several
+    /// orders that were sent without them.
+    async fn place_tp_sl(
+        &self,
+        market_name: &str,
+        market: &Self::MarketData,
+        side: Side,
+        qty: f64,
+        tp_sl: TpSl,
+    ) -> anyhow::Result<()>;
+
+    async fn cancel_order(&self, market_name: &str, order_id: &str) -> anyhow::Result<()>;
+
+    /// Cancels every open order on the market, TP/SL orders included.
+    async fn cancel_all_orders(&self, market_name: &str) -> anyhow::Result<()>;
+}
@@ -0,0 +1,256 @@
+use std::str::FromStr;
+
+use rust_decimal::Decimal;
+
+use crate::{
+    error::bot_error::{BotError, f64_to_decimal},
+    utils::utils::{RoundingMode, round_to_min_change},
+};
+
+/// Direction of an order, independent of how each venue spells it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+impl Side {
+    pub fn opposite(self) -> Side {
+        match self {
+            Side::Buy => Side::Sell,
+            Side::Sell => Side::Buy,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PositionSide {
+    Long,
+    Short,
+}
+
+impl PositionSide {
+    /// Side of the order that opens (or adds to) a position on this side.
+    pub fn opening_side(self) -> Side {
+        match self {
+            PositionSide::Long => Side::Buy,
+            PositionSide::Short => Side::Sell,
+        }
+    }
+
+    /// Side of the order that reduces or closes a position on this side.
+    pub fn closing_side(self) -> Side {
+        self.opening_side().opposite()
+    }
+}
+
+impl FromStr for PositionSide {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "LONG" => Ok(PositionSide::Long),
+            "SHORT" => Ok(PositionSide::Short),
+            _ => Err(BotError::parse("side", format!("unknown position side {}", s)).into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Position {
+    pub market: String,
+    pub side: PositionSide,
+    /// Position size in base units, negative for shorts.
+    pub size: f64,
+    pub entry_price: f64,
+    /// When the position was opened, in Unix milliseconds.
+    pub opened_at: u64,
+}
+
+impl Position {
+    pub fn new(
+        market: String,
+        side: PositionSide,
+        quantity: f64,
+        entry_price: f64,
+        opened_at: u64,
+    ) -> Position {
+        let size = match side {
+            PositionSide::Long => quantity.abs(),
+            PositionSide::Short => -quantity.abs(),
+        };
+
+        Position {
+            market,
+            side,
+            size,
+            entry_price,
+            opened_at,
+        }
+    }
+
+    /// Unsigned position size in base units.
+    pub fn quantity(&self) -> f64 {
+        self.size.abs()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Balance {
+    pub balance: f64,
+    /// Collateral currently available to open new positions.
+    pub available_for_trade: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct MarketSnapshot {
+    pub market: String,
+    pub bid: f64,
+    pub ask: f64,
+    pub mark: f64,
+    /// Funding rate of the coming payment as a fraction, positive when longs pay shorts.
+    pub funding_rate: f64,
+    /// Length of the period `funding_rate` is paid for.
+    pub funding_interval_hours: f64,
+    /// Largest rate the venue charges per period either way, if it caps it.
+    pub max_funding_rate: Option<f64>,
+    /// When the venue pays the rate next, in epoch milliseconds, if it publishes it.
+    pub next_funding_time: Option<u64>,
+    /// Smallest order size increment in base units.
+    pub lot_size: f64,
+    /// Largest notional in USD of a single order, if the venue limits it.
+    pub max_order_value: Option<f64>,
+    /// Largest notional in USD of a position, if the venue limits it.
+    pub max_position_value: Option<f64>,
+}
+
+/// Trading fees as fractions of the notional.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FeeRates {
+    pub maker: f64,
+    pub taker: f64,
+}
+
+/// Hourly OHLC candle, used to measure volatility.
+#[derive(Debug, Clone)]
+pub struct Candle {
+    /// Start of the candle in epoch milliseconds.
+    pub open_time: u64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+/// What an order does to the position it trades.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrderIntent {
+    /// Opens or adds to a position, with the protective orders to attach.
+    Open(Option<TpSl>),
+    /// Reduces or closes a position.
+    Reduce,
+}
+
+impl OrderIntent {
+    pub fn tp_sl(self) -> Option<TpSl> {
+        match self {
+            OrderIntent::Open(tp_sl) => tp_sl,
+            OrderIntent::Reduce => None,
+        }
+    }
+}
+
+/// Take profit and stop loss distances from the entry price, as fractions of
+/// that price. Both venues build their protective orders from it, so the two
+/// legs of a pair are protected the same way.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TpSl {
+    pub take_profit: f64,
+    pub stop_loss: f64,
+    /// How far past the trigger price the limit price sits, so the triggered
+    /// order still fills in a fast market.
+    pub limit_offset: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TpSlPrices {
+    pub take_profit_trigger: Decimal,
+    pub take_profit_limit: Decimal,
+    pub stop_loss_trigger: Decimal,
+    pub stop_loss_limit: Decimal,
+}
+
+impl TpSl {
+    /// Trigger and limit prices protecting a position opened at `price`,
+    /// rounded to the venue's `tick_size`.
+    pub fn prices(
+        &self,
+        is_buying: bool,
+        price: Decimal,
+        tick_size: Decimal,
+    ) -> Result<TpSlPrices, BotError> {
+        let take_profit = f64_to_decimal(self.take_profit, "take_profit")?;
+        let stop_loss = f64_to_decimal(self.stop_loss, "stop_loss")?;
+        let limit_offset = f64_to_decimal(self.limit_offset, "limit_offset")?;
+
+        // A long takes profit above the entry and stops out below it, a short
+        // the other way around. Limits sit past the trigger, towards the book.
+        let direction = if is_buying {
+            Decimal::ONE
+        } else {
+            -Decimal::ONE
+        };
+        let rounding_mode = if is_buying {
+            RoundingMode::Floor
+        } else {
+            RoundingMode::Ceil
+        };
+        let at = |fraction: Decimal| {
+            round_to_min_change(
+                price * (Decimal::ONE + direction * fraction),
+                tick_size,
+                Some(rounding_mode),
+            )
+        };
+
+        Ok(TpSlPrices {
+            take_profit_trigger: at(take_profit),
+            take_profit_limit: at(take_profit - limit_offset),
+            stop_loss_trigger: at(-stop_loss),
+            stop_loss_limit: at(-stop_loss - limit_offset),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderStatus {
+    Accepted,
+    Rejected,
+}
+
+/// What a venue answered when an order was submitted.
+#[derive(Debug, Clone)]
+pub struct OrderAck {
+    pub venue_order_id: Option<String>,
+    /// Id the order was submitted with, known even when the venue rejects it.
+    pub client_order_id: String,
+    pub status: OrderStatus,
+    pub rejection_reason: Option<String>,
+    /// Raw response body as sent by the venue.
+    pub body: String,
+}
+
+impl OrderAck {
+    /// Turns a rejected order into an error that carries the venue response.
+    pub fn accepted(self) -> Result<OrderAck, BotError> {
+        match self.status {
+            OrderStatus::Accepted => Ok(self),
+            OrderStatus::Rejected => Err(BotError::Rejected {
+                code: None,
+                message: self
+                    .rejection_reason
+                    .unwrap_or_else(|| format!("order {} rejected", self.client_order_id)),
+                body: Some(self.body),
+            }),
+        }
+    }
+}
@@ -0,0 +1,184 @@
+mod common;
+
+use chrono::Utc;
+
+use funding_rate_bot::{
+    config::structs::ExitPolicy,
+    strategy::{close_if_necessary::close_if_necessary, structs::ExitReason},
+    venue::structs::{Position, PositionSide},
+};
+
+use common::mock_exchange::{EXTENDED_MARKET, MockExchange, PACIFICA_MARKET, Scenario, eth_pair};
+
+/// Short on Extended and long on Pacifica, opened `held_hours` ago.
+fn open_pair(held_hours: f64, extended_entry: f64) -> (Position, Position) {
+    let opened_at = Utc::now().timestamp_millis() as u64 - (held_hours * 3_600_000.0) as u64;
+    (
+        Position::new(
+            EXTENDED_MARKET.to_string(),
+            PositionSide::Short,
+            0.012,
+            extended_entry,
+            opened_at,
+        ),
+        Position::new(
+            PACIFICA_MARKET.to_string(),
+            PositionSide::Long,
+            0.012,
+            2000.0,
+            opened_at,
+        ),
+    )
+}
+
+/// Pacifica now pays slightly more funding than Extended.
+fn flipped_funding() -> Scenario {
+    Scenario {
+        extended_funding_rate: 0.00009,
+        pacifica_funding_rate: 0.0001,
+        ..Scenario::default()
+    }
+}
+
+async fn run(
+    exchange: &MockExchange,
+    positions: (Position, Position),
+    exit: ExitPolicy,
+) -> Option<ExitReason> {
+    try_run(exchange, positions, exit).await.unwrap()
+}
+
+async fn try_run(
+    exchange: &MockExchange,
+    positions: (Position, Position),
+    exit: ExitPolicy,
+) -> anyhow::Result<Option<ExitReason>> {
+    // The venues hold the positions the bot is closing
+    {
+        let mut state = exchange.state();
+        state
+            .extended_positions
+            .insert(positions.0.market.clone(), positions.0.size);
+        state
+            .pacifica_positions
+            .insert(positions.1.market.clone(), positions.1.size);
+    }
+
+    let pair = eth_pair();
+    close_if_necessary(
+        &exchange.extended_venue(),
+        &pair.extended,
+        &positions.0,
+        &exchange.pacifica_venue(),
+        &pair.pacifica,
+        &positions.1,
+        &exit,
+    )
+    .await
+}
+
+#[tokio::test]
+async fn keeps_pair_while_funding_favors_it() {
+    let exchange = MockExchange::start(Scenario::default()).await;
+
+    let reason = run(&exchange, open_pair(24.0, 2000.0), ExitPolicy::default()).await;
+
+    assert_eq!(reason, None);
+    assert!(exchange.state().extended_orders.is_empty());
+    assert!(exchange.state().pacifica_orders.is_empty());
+}
+
+#[tokio::test]
+async fn closes_both_legs_when_funding_falls_below_exit_threshold() {
+    let exchange = MockExchange::start(flipped_funding()).await;
+
+    let reason = run(&exchange, open_pair(24.0, 2000.0), ExitPolicy::default()).await;
+
+    assert!(matches!(reason, Some(ExitReason::FundingBelowExit { .. })));
+    let state = exchange.state();
+    assert_eq!(state.extended_orders[0]["side"], "BUY");
+    assert_eq!(state.extended_orders[0]["reduceOnly"], true);
+    assert!(state.extended_orders[0]["takeProfit"].is_null());
+    assert_eq!(state.pacifica_orders[0]["side"], "ask");
+    assert_eq!(state.pacifica_orders[0]["reduce_only"], true);
+    assert_eq!(state.extended_positions[EXTENDED_MARKET], 0.0);
+    assert_eq!(state.pacifica_positions[PACIFICA_MARKET], 0.0);
+}
+
+#[tokio::test]
+async fn exit_threshold_keeps_pair_through_small_flips() {
+    let exchange = MockExchange::start(flipped_funding()).await;
+
+    let exit = ExitPolicy {
+        exit_threshold: -0.005,
+        ..ExitPolicy::default()
+    };
+    let reason = run(&exchange, open_pair(24.0, 2000.0), exit).await;
+
+    assert_eq!(reason, None);
+    assert!(exchange.state().extended_orders.is_empty());
+}
+
+#[tokio::test]
+async fn funding_exit_waits_for_minimum_hold() {
+    let exchange = MockExchange::start(flipped_funding()).await;
+
+    let exit = ExitPolicy {
+        min_funding_periods: 2,
+        ..ExitPolicy::default()
+    };
+    assert_eq!(run(&exchange, open_pair(1.5, 2000.0), exit).await, None);
+    assert!(exchange.state().extended_orders.is_empty());
+
+    let reason = run(&exchange, open_pair(2.5, 2000.0), exit).await;
+    assert!(matches!(reason, Some(ExitReason::FundingBelowExit { .. })));
+}
+
+#[tokio::test]
+async fn takes_basis_profit() {
+    let exchange = MockExchange::start(Scenario::default()).await;
+
+    // The short was entered 5% above the current price
+    let exit = ExitPolicy {
+        basis_take_profit: 2.0,
+        ..ExitPolicy::default()
+    };
+    let reason = run(&exchange, open_pair(1.0, 2100.0), exit).await;
+
+    assert!(matches!(reason, Some(ExitReason::BasisTakeProfit { .. })));
+    assert_eq!(exchange.state().extended_orders.len(), 1);
+    assert_eq!(exchange.state().pacifica_orders.len(), 1);
+}
+
+#[tokio::test]
+async fn closes_after_max_holding_time() {
+    let exchange = MockExchange::start(Scenario::default()).await;
+
+    let exit = ExitPolicy {
+        max_holding_hours: 72.0,
+        ..ExitPolicy::default()
+    };
+    assert_eq!(run(&exchange, open_pair(48.0, 2000.0), exit).await, None);
+
+    let reason = run(&exchange, open_pair(100.0, 2000.0), exit).await;
+    assert!(matches!(reason, Some(ExitReason::MaxHoldingTime { .. })));
+}
+
+#[tokio::test]
+async fn closes_the_second_leg_when_the_first_cancel_fails() {
+    let exchange = MockExchange::start(Scenario {
+        reject_extended_cancels: true,
+        ..flipped_funding()
+    })
+    .await;
+
+    let error = try_run(&exchange, open_pair(24.0, 2000.0), ExitPolicy::default())
+        .await
+        .unwrap_err();
+
+    assert!(format!("{:#}", error).contains("Mass cancel failed"));
+    let state = exchange.state();
+    assert_eq!(state.extended_positions[EXTENDED_MARKET], 0.0);
+    assert_eq!(state.pacifica_positions[PACIFICA_MARKET], 0.0);
+    assert_eq!(state.pacifica_cancels.len(), 1);
+}
@@ -0,0 +1,778 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{delete, get, post},
+};
+use serde_json::{Value, json};
+
+use funding_rate_bot::{
+    config::structs::{
+        EntryMode, ExitPolicy, MarketPair, OrphanPolicy, PairLeg, SizingPolicy, TpSlPolicy,
+        VenueHttpConfig,
+    },
+    extended::venue::ExtendedVenue,
+    pacifica::venue::PacificaVenue,
+    utils::venue_client::VenueClient,
+    venue::structs::FeeRates,
+};
+
+pub const EXTENDED_MARKET: &str = "ETH-USD";
+pub const PACIFICA_MARKET: &str = "ETH";
+
+// Any valid Stark private key works, the mock never checks signatures
+const STARK_PRIVATE_KEY: &str = "0x3c1e9550e66958296d11b60f8e8e7a7ad990d07fa65d5f7652c4a6c87d4e3cc";
+
+/// How a venue answers order requests.
+#[derive(Clone, Copy, Debug)]
+pub enum OrderBehavior {
+    Fill,
+    /// Only this fraction of the requested size is filled.
+    PartialFill(f64),
+    /// The order is rejected with the venue's error body.
+    Reject,
+    /// Opening orders fill, reduce-only orders are rejected.
+    RejectReduceOnly,
+}
+
+/// Market state and venue behavior for one test.
+#[derive(Clone, Debug)]
+pub struct Scenario {
+    pub extended_price: f64,
+    /// Rate projected for the next Extended payment.
+    pub extended_funding_rate: f64,
+    /// Hours between the payments in the Extended funding history.
+    pub extended_funding_interval_hours: f64,
+    /// Funding cap published by both venues, if any.
+    pub max_funding_rate: Option<f64>,
+    pub pacifica_price: f64,
+    pub pacifica_funding_rate: f64,
+    pub balance: f64,
+    /// High-low range of every Extended candle.
+    pub candle_range: f64,
+    pub extended_order: OrderBehavior,
+    pub pacifica_order: OrderBehavior,
+    /// Whether cancel-all requests are rejected, after being recorded.
+    pub reject_extended_cancels: bool,
+    pub reject_pacifica_cancels: bool,
+    /// Delay added before every response.
+    pub response_delay: Duration,
+}
+
+impl Default for Scenario {
+    fn default() -> Self {
+        Scenario {
+            extended_price: 2000.0,
+            extended_funding_rate: 0.0001,
+            extended_funding_interval_hours: 1.0,
+            max_funding_rate: None,
+            pacifica_price: 2000.0,
+            pacifica_funding_rate: 0.00001,
+            balance: 1000.0,
+            candle_range: 20.0,
+            extended_order: OrderBehavior::Fill,
+            pacifica_order: OrderBehavior::Fill,
+            reject_extended_cancels: false,
+            reject_pacifica_cancels: false,
+            response_delay: Duration::ZERO,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct MockState {
+    pub scenario: Scenario,
+    /// Order bodies received, in order.
+    pub extended_orders: Vec<Value>,
+    pub pacifica_orders: Vec<Value>,
+    /// Ids of the Extended orders cancelled, external ids prefixed with `external:`.
+    pub extended_cancels: Vec<String>,
+    /// Mass-cancel request bodies received by Extended.
+    pub extended_mass_cancels: Vec<Value>,
+    /// Orders listed by Extended as still open, set up by the test.
+    pub extended_open_orders: Vec<Value>,
+    /// Cancel request bodies received by Pacifica, including cancel-all.
+    pub pacifica_cancels: Vec<Value>,
+    /// Limit and stop orders resting on Pacifica.
+    pub pacifica_open_orders: Vec<Value>,
+    /// Signed position size per market, negative for shorts.
+    pub extended_positions: HashMap<String, f64>,
+    pub pacifica_positions: HashMap<String, f64>,
+}
+
+type SharedState = Arc<Mutex<MockState>>;
+
+/// Extended and Pacifica REST APIs served from two local ports.
+pub struct MockExchange {
+    pub extended_url: String,
+    pub pacifica_url: String,
+    pub state: SharedState,
+}
+
+impl MockExchange {
+    pub async fn start(scenario: Scenario) -> MockExchange {
+        let state = Arc::new(Mutex::new(MockState {
+            scenario,
+            ..MockState::default()
+        }));
+
+        let extended_url = serve(extended_router(state.clone())).await;
+        let pacifica_url = serve(pacifica_router(state.clone())).await;
+
+        MockExchange {
+            extended_url,
+            pacifica_url,
+            state,
+        }
+    }
+
+    pub fn extended_venue(&self) -> ExtendedVenue {
+        ExtendedVenue {
+            client: VenueClient::new(&http_config(&self.extended_url)).unwrap(),
+            api_key: String::from("test-api-key"),
+            stark_private_key: String::from(STARK_PRIVATE_KEY),
+            vault_id: String::from("1"),
+            stark_public_key: String::from("0x1"),
+        }
+    }
+
+    pub fn pacifica_venue(&self) -> PacificaVenue {
+        PacificaVenue {
+            client: VenueClient::new(&http_config(&self.pacifica_url)).unwrap(),
+            private_key: solana_sdk::signature::Keypair::new().to_base58_string(),
+            wallet_address: String::from("test-wallet"),
+            fees: FeeRates {
+                maker: 0.00015,
+                taker: 0.0004,
+            },
+        }
+    }
+
+    pub fn state(&self) -> std::sync::MutexGuard<'_, MockState> {
+        self.state.lock().unwrap()
+    }
+}
+
+/// The ETH pair with the config defaults the bot ships with.
+pub fn eth_pair() -> MarketPair {
+    MarketPair {
+        extended: PairLeg {
+            market: String::from(EXTENDED_MARKET),
+            slippage: 0.01,
+        },
+        pacifica: PairLeg {
+            market: String::from(PACIFICA_MARKET),
+            slippage: 0.0001,
+        },
+        buy_amount: 25.0,
+        funding_rate_threshold: 0.001,
+        price_spread_threshold: 0.02,
+        orphan_policy: OrphanPolicy::Alert,
+        entry_mode: EntryMode::Taker,
+        maker_timeout_secs: 60,
+        twap_slices: 4,
+        twap_window_secs: 120,
+        tp_sl: TpSlPolicy::default(),
+        expected_holding_hours: 72.0,
+        estimated_slippage: 0.0001,
+        min_net_edge: 0.0,
+        exit: ExitPolicy::default(),
+        sizing: SizingPolicy::default(),
+    }
+}
+
+fn http_config(base_url: &str) -> VenueHttpConfig {
+    VenueHttpConfig {
+        base_url: base_url.to_string(),
+        request_timeout_secs: 1,
+        connect_timeout_secs: 1,
+    }
+}
+
+async fn serve(router: Router) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+    format!("http://{}", address)
+}
+
+async fn delay(state: &SharedState) {
+    let response_delay = state.lock().unwrap().scenario.response_delay;
+    if !response_delay.is_zero() {
+        tokio::time::sleep(response_delay).await;
+    }
+}
+
+/// Filled size for an order of `qty` under `behavior`, `None` when rejected.
+fn filled_qty(behavior: OrderBehavior, qty: f64, reduce_only: bool) -> Option<f64> {
+    match behavior {
+        OrderBehavior::Fill => Some(qty),
+        OrderBehavior::PartialFill(fraction) => Some(qty * fraction),
+        OrderBehavior::Reject => None,
+        OrderBehavior::RejectReduceOnly => (!reduce_only).then_some(qty),
+    }
+}
+
+/// Signed fill of an order against a `position`. Reduce-only orders are
+/// clipped to the position they reduce and rejected when there is none.
+fn signed_fill(filled: f64, is_buy: bool, reduce_only: bool, position: f64) -> Option<f64> {
+    let signed = if is_buy { filled } else { -filled };
+    if !reduce_only {
+        return Some(signed);
+    }
+    if position == 0.0 || position.signum() == signed.signum() {
+        return None;
+    }
+
+    Some(signed.signum() * signed.abs().min(position.abs()))
+}
+
+fn extended_router(state: SharedState) -> Router {
+    Router::new()
+        .route("/api/v1/info/markets", get(extended_markets))
+        .route("/api/v1/info/starknet", get(extended_starknet))
+        .route(
+            "/api/v1/info/{market}/funding",
+            get(extended_funding_history),
+        )
+        .route("/api/v1/user/fees", get(extended_fees))
+        .route("/api/v1/user/positions", get(extended_positions))
+        .route("/api/v1/user/balance", get(extended_balance))
+        .route(
+            "/api/v1/user/order",
+            post(extended_place_order).delete(extended_cancel_by_external_id),
+        )
+        .route("/api/v1/user/order/{id}", delete(extended_cancel_order))
+        .route("/api/v1/user/order/massCancel", post(extended_mass_cancel))
+        .route("/api/v1/user/orders", get(extended_open_orders))
+        .route(
+            "/api/v1/info/candles/{market}/trades",
+            get(extended_candles),
+        )
+        .with_state(state)
+}
+
+/// Lists the ETH market, or nothing when another `market` is asked for.
+async fn extended_markets(
+    State(state): State<SharedState>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Json<Value> {
+    delay(&state).await;
+    let scenario = state.lock().unwrap().scenario.clone();
+    let price = scenario.extended_price.to_string();
+
+    if query
+        .get("market")
+        .is_some_and(|market| market != EXTENDED_MARKET)
+    {
+        return Json(json!({ "status": "OK", "data": [] }));
+    }
+
+    Json(json!({
+        "status": "OK",
+        "data": [{
+            "name": EXTENDED_MARKET,
+            "status": "ACTIVE",
+            "marketStats": {
+                "askPrice": price,
+                "bidPrice": price,
+                "markPrice": price,
+                "lastPrice": price,
+                "indexPrice": price,
+                // The last payment differs from the projection the strategy reads
+                "fundingRate": (scenario.extended_funding_rate / 2.0).to_string(),
+                "nextFundingRate": scenario.extended_funding_rate.to_string(),
+            },
+            "tradingConfig": {
+                "minOrderSizeChange": "0.001",
+                "maxPositionValue": "1000000",
+                "minPriceChange": "0.1",
+                "maxFundingRate": scenario.max_funding_rate.map(|rate| rate.to_string()),
+            },
+            "l2Config": {
+                "collateralId": "0x31857064564ed0ff978e687456963cba09c2c6985d8f9300a1de4962fafa054",
+                "syntheticId": "0x4554482d3800000000000000000000",
+                "syntheticResolution": 100000000,
+                "collateralResolution": 1000000,
+            },
+        }],
+    }))
+}
+
+/// Three payments a funding interval apart, ending now.
+async fn extended_funding_history(
+    State(state): State<SharedState>,
+    Path(market): Path<String>,
+) -> Json<Value> {
+    delay(&state).await;
+    let scenario = state.lock().unwrap().scenario.clone();
+    let interval = (scenario.extended_funding_interval_hours * 3_600_000.0) as u64;
+    let now = chrono::Utc::now().timestamp_millis() as u64;
+    let payments = (0..3u64)
+        .map(|i| {
+            json!({
+                "m": market,
+                "f": scenario.extended_funding_rate.to_string(),
+                "T": now - (2 - i) * interval,
+            })
+        })
+        .collect::<Vec<Value>>();
+
+    Json(json!({ "status": "OK", "data": payments }))
+}
+
+/// Hourly candles around the scenario price, each with a high-low range of
+/// `candle_range` and no gaps between closes.
+async fn extended_candles(State(state): State<SharedState>) -> Json<Value> {
+    delay(&state).await;
+    let scenario = state.lock().unwrap().scenario.clone();
+    let half_range = scenario.candle_range / 2.0;
+    let candles = (0..20u64)
+        .rev()
+        .map(|hour| {
+            json!({
+                "T": 1_700_000_000_000u64 + hour * 3_600_000,
+                "o": scenario.extended_price.to_string(),
+                "h": (scenario.extended_price + half_range).to_string(),
+                "l": (scenario.extended_price - half_range).to_string(),
+                "c": scenario.extended_price.to_string(),
+                "v": "1",
+            })
+        })
+        .collect::<Vec<Value>>();
+
+    Json(json!({ "status": "OK", "data": candles }))
+}
+
+async fn extended_starknet(State(state): State<SharedState>) -> Json<Value> {
+    delay(&state).await;
+    Json(json!({
+        "status": "OK",
+        "data": { "name": "Perpetuals", "version": "v0", "chainId": "SN_MAIN", "revision": 1 },
+    }))
+}
+
+/// The account's fee rates are only served to requests with an API key.
+async fn extended_fees(State(state): State<SharedState>, headers: HeaderMap) -> Response {
+    delay(&state).await;
+    let has_api_key = headers
+        .get("X-Api-Key")
+        .is_some_and(|api_key| !api_key.is_empty());
+    if !has_api_key {
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "status": "ERROR",
+                "error": { "code": 401, "message": "Missing API key" },
+            })),
+        )
+            .into_response();
+    }
+
+    Json(json!({
+        "status": "OK",
+        "data": [{
+            "market": EXTENDED_MARKET,
+            "makerFeeRate": "0.0000",
+            "takerFeeRate": "0.00025",
+            "builderFeeRate": "0",
+        }],
+    }))
+    .into_response()
+}
+
+async fn extended_positions(State(state): State<SharedState>) -> Json<Value> {
+    delay(&state).await;
+    let state = state.lock().unwrap();
+    let price = state.scenario.extended_price.to_string();
+    let positions = state
+        .extended_positions
+        .iter()
+        .filter(|(_, size)| **size != 0.0)
+        .map(|(market, size)| {
+            json!({
+                "id": 1,
+                "accountId": 1,
+                "market": market,
+                "side": if *size > 0.0 { "LONG" } else { "SHORT" },
+                "leverage": "1",
+                "size": size.abs().to_string(),
+                "value": (size.abs() * state.scenario.extended_price).to_string(),
+                "openPrice": price,
+                "markPrice": price,
+                "liquidationPrice": "0",
+                "margin": "0",
+                "unrealisedPnl": "0",
+                "realisedPnl": "0",
+                "adl": 0,
+                "createdAt": 0,
+                "updatedAt": 0,
+            })
+        })
+        .collect::<Vec<Value>>();
+
+    Json(json!({ "status": "OK", "data": positions }))
+}
+
+async fn extended_balance(State(state): State<SharedState>) -> Json<Value> {
+    delay(&state).await;
+    let balance = state.lock().unwrap().scenario.balance.to_string();
+    Json(json!({
+        "status": "OK",
+        "data": { "balance": balance, "availableForTrade": balance },
+    }))
+}
+
+async fn extended_place_order(
+    State(state): State<SharedState>,
+    Json(order): Json<Value>,
+) -> Response {
+    delay(&state).await;
+    let mut state = state.lock().unwrap();
+    state.extended_orders.push(order.clone());
+
+    // Conditional orders are accepted but never trigger
+    if order["type"] == "TPSL" {
+        return Json(json!({
+            "status": "OK",
+            "data": { "id": state.extended_orders.len(), "externalId": order["id"] },
+        }))
+        .into_response();
+    }
+
+    let qty = order["qty"].as_str().unwrap().parse::<f64>().unwrap();
+    let Some(filled) = filled_qty(
+        state.scenario.extended_order,
+        qty,
+        order["reduceOnly"] == true,
+    ) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "status": "ERROR",
+                "error": { "code": 1140, "message": "Order rejected by mock" },
+            })),
+        )
+            .into_response();
+    };
+
+    let market = order["market"].as_str().unwrap().to_string();
+    let position = state.extended_positions.entry(market).or_default();
+    let Some(signed) = signed_fill(
+        filled,
+        order["side"] == "BUY",
+        order["reduceOnly"] == true,
+        *position,
+    ) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "status": "ERROR",
+                "error": { "code": 1137, "message": "Position is missing for reduce-only order" },
+            })),
+        )
+            .into_response();
+    };
+    *position += signed;
+
+    Json(json!({
+        "status": "OK",
+        "data": { "id": state.extended_orders.len(), "externalId": order["id"] },
+    }))
+    .into_response()
+}
+
+async fn extended_cancel_order(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+) -> Json<Value> {
+    delay(&state).await;
+    state.lock().unwrap().extended_cancels.push(id);
+    Json(json!({ "status": "OK" }))
+}
+
+async fn extended_cancel_by_external_id(
+    State(state): State<SharedState>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Json<Value> {
+    delay(&state).await;
+    let external_id = query["externalId"].clone();
+    let mut state = state.lock().unwrap();
+    state
+        .extended_open_orders
+        .retain(|order| order["externalId"] != external_id.as_str());
+    state
+        .extended_cancels
+        .push(format!("external:{}", external_id));
+    Json(json!({ "status": "OK" }))
+}
+
+async fn extended_mass_cancel(
+    State(state): State<SharedState>,
+    Json(request): Json<Value>,
+) -> Json<Value> {
+    delay(&state).await;
+    let mut state = state.lock().unwrap();
+    if state.scenario.reject_extended_cancels {
+        state.extended_mass_cancels.push(request);
+        return Json(json!({
+            "status": "ERROR",
+            "error": { "code": 1500, "message": "Mass cancel failed" },
+        }));
+    }
+    let markets = request["markets"].as_array().cloned().unwrap_or_default();
+    state
+        .extended_open_orders
+        .retain(|order| request["cancelAll"] != true && !markets.contains(&order["market"]));
+    state.extended_mass_cancels.push(request);
+    Json(json!({ "status": "OK" }))
+}
+
+async fn extended_open_orders(
+    State(state): State<SharedState>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Json<Value> {
+    delay(&state).await;
+    let state = state.lock().unwrap();
+    let orders = state
+        .extended_open_orders
+        .iter()
+        .filter(|order| {
+            query
+                .get("market")
+                .is_none_or(|market| order["market"] == market.as_str())
+        })
+        .cloned()
+        .collect::<Vec<Value>>();
+    Json(json!({ "status": "OK", "data": orders }))
+}
+
+fn pacifica_router(state: SharedState) -> Router {
+    Router::new()
+        .route("/api/v1/info/prices", get(pacifica_prices))
+        .route("/api/v1/info", get(pacifica_info))
+        .route(
+            "/api/v1/funding_rate/history",
+            get(pacifica_funding_history),
+        )
+        .route("/api/v1/positions", get(pacifica_positions))
+        .route("/api/v1/account", get(pacifica_account))
+        .route("/api/v1/orders/create_market", post(pacifica_create_market))
+        .route("/api/v1/orders/create", post(pacifica_create_order))
+        .route("/api/v1/orders/stop/create", post(pacifica_create_order))
+        .route("/api/v1/orders/cancel", post(pacifica_cancel_order))
+        .route("/api/v1/orders/stop/cancel", post(pacifica_cancel_order))
+        .route(
+            "/api/v1/orders/cancel_all",
+            post(pacifica_cancel_all_orders),
+        )
+        .with_state(state)
+}
+
+async fn pacifica_prices(State(state): State<SharedState>) -> Json<Value> {
+    delay(&state).await;
+    let scenario = state.lock().unwrap().scenario.clone();
+    Json(json!({
+        "success": true,
+        "data": [{
+            "symbol": PACIFICA_MARKET,
+            "mid": scenario.pacifica_price.to_string(),
+            "mark": scenario.pacifica_price.to_string(),
+            "next_funding": scenario.pacifica_funding_rate.to_string(),
+        }],
+    }))
+}
+
+async fn pacifica_info(State(state): State<SharedState>) -> Json<Value> {
+    delay(&state).await;
+    let scenario = state.lock().unwrap().scenario.clone();
+    Json(json!({
+        "success": true,
+        "data": [{
+            "symbol": PACIFICA_MARKET,
+            "tick_size": "0.1",
+            "min_tick": "0",
+            "max_tick": "1000000",
+            "lot_size": "0.0001",
+            "min_order_size": "10",
+            "max_order_size": "5000000",
+            "max_funding_rate": scenario.max_funding_rate.map(|rate| rate.to_string()),
+        }],
+    }))
+}
+
+/// Three hourly payments, newest first like the live endpoint.
+async fn pacifica_funding_history(State(state): State<SharedState>) -> Json<Value> {
+    delay(&state).await;
+    let scenario = state.lock().unwrap().scenario.clone();
+    let now = chrono::Utc::now().timestamp_millis() as u64;
+    let payments = (0..3u64)
+        .map(|i| {
+            json!({
+                "funding_rate": scenario.pacifica_funding_rate.to_string(),
+                "created_at": now - i * 3_600_000,
+            })
+        })
+        .collect::<Vec<Value>>();
+
+    Json(json!({ "success": true, "data": payments }))
+}
+
+async fn pacifica_positions(State(state): State<SharedState>) -> Json<Value> {
+    delay(&state).await;
+    let state = state.lock().unwrap();
+    let positions = state
+        .pacifica_positions
+        .iter()
+        .filter(|(_, size)| **size != 0.0)
+        .map(|(symbol, size)| {
+            json!({
+                "symbol": symbol,
+                "side": if *size > 0.0 { "bid" } else { "ask" },
+                "amount": size.abs().to_string(),
+                "entry_price": state.scenario.pacifica_price.to_string(),
+                "margin": "0",
+                "funding": "0",
+                "isolated": false,
+                "created_at": 0,
+                "updated_at": 0,
+            })
+        })
+        .collect::<Vec<Value>>();
+
+    Json(json!({ "success": true, "data": positions }))
+}
+
+async fn pacifica_account(State(state): State<SharedState>) -> Json<Value> {
+    delay(&state).await;
+    let balance = state.lock().unwrap().scenario.balance.to_string();
+    Json(json!({
+        "success": true,
+        "data": { "balance": balance, "available_to_spend": balance },
+    }))
+}
+
+async fn pacifica_create_market(
+    State(state): State<SharedState>,
+    Json(order): Json<Value>,
+) -> Response {
+    delay(&state).await;
+    let mut state = state.lock().unwrap();
+    state.pacifica_orders.push(order.clone());
+
+    let amount = order["amount"].as_str().unwrap().parse::<f64>().unwrap();
+    let Some(filled) = filled_qty(
+        state.scenario.pacifica_order,
+        amount,
+        order["reduce_only"] == true,
+    ) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "success": false,
+                "data": null,
+                "error": "Order rejected by mock",
+                "code": 400,
+            })),
+        )
+            .into_response();
+    };
+
+    let symbol = order["symbol"].as_str().unwrap().to_string();
+    let position = state.pacifica_positions.entry(symbol).or_default();
+    let Some(signed) = signed_fill(
+        filled,
+        order["side"] == "bid",
+        order["reduce_only"] == true,
+        *position,
+    ) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "success": false,
+                "data": null,
+                "error": "Reduce-only order would increase the position",
+                "code": 400,
+            })),
+        )
+            .into_response();
+    };
+    *position += signed;
+
+    Json(json!({
+        "success": true,
+        "data": { "order_id": state.pacifica_orders.len() },
+        "error": null,
+        "code": null,
+    }))
+    .into_response()
+}
+
+/// Limit and stop orders rest on the book until cancelled, they never fill.
+async fn pacifica_create_order(
+    State(state): State<SharedState>,
+    Json(order): Json<Value>,
+) -> Json<Value> {
+    delay(&state).await;
+    let mut state = state.lock().unwrap();
+    state.pacifica_orders.push(order.clone());
+    state.pacifica_open_orders.push(order);
+
+    Json(json!({
+        "success": true,
+        "data": { "order_id": state.pacifica_orders.len() },
+        "error": null,
+        "code": null,
+    }))
+}
+
+async fn pacifica_cancel_order(
+    State(state): State<SharedState>,
+    Json(cancel): Json<Value>,
+) -> Json<Value> {
+    delay(&state).await;
+    let mut state = state.lock().unwrap();
+    state
+        .pacifica_open_orders
+        .retain(|order| order["client_order_id"] != cancel["client_order_id"]);
+    state.pacifica_cancels.push(cancel);
+    Json(json!({ "success": true, "data": null, "error": null, "code": null }))
+}
+
+async fn pacifica_cancel_all_orders(
+    State(state): State<SharedState>,
+    Json(cancel): Json<Value>,
+) -> Json<Value> {
+    delay(&state).await;
+    let mut state = state.lock().unwrap();
+    if state.scenario.reject_pacifica_cancels {
+        state.pacifica_cancels.push(cancel);
+        return Json(json!({
+            "success": false,
+            "data": null,
+            "error": "Cancel all failed",
+            "code": 500,
+        }));
+    }
+    let open_orders = state.pacifica_open_orders.len();
+    state
+        .pacifica_open_orders
+        .retain(|order| cancel["all_symbols"] != true && order["symbol"] != cancel["symbol"]);
+    let cancelled_count = open_orders - state.pacifica_open_orders.len();
+    state.pacifica_cancels.push(cancel);
+
+    Json(json!({
+        "success": true,
+        "data": { "cancelled_count": cancelled_count },
+        "error": null,
+        "code": null,
+    }))
+}
@@ -0,0 +1,108 @@
+use funding_rate_bot::discovery::{
+    discover_pairs::{contract_ratio, extended_market_key, match_markets, pacifica_market_key},
+    structs::{DiscoveredPair, MarketKey},
+};
+
+fn key(multiplier: u64, base: &str) -> MarketKey {
+    MarketKey {
+        multiplier,
+        base: base.to_string(),
+    }
+}
+
+fn names(markets: &[&str]) -> Vec<String> {
+    markets.iter().map(|market| market.to_string()).collect()
+}
+
+#[test]
+fn parses_extended_market_keys() {
+    let cases = [
+        ("ETH-USD", Some(key(1, "ETH"))),
+        ("1000BONK-USD", Some(key(1_000, "BONK"))),
+        ("1000000MOG-USD", Some(key(1_000_000, "MOG"))),
+        // A number that is part of the asset name is not a multiplier
+        ("1000-USD", Some(key(1, "1000"))),
+        ("1INCH-USD", Some(key(1, "1INCH"))),
+        ("ETH-EUR", None),
+        ("ETH", None),
+    ];
+
+    for (name, expected) in cases {
+        assert_eq!(extended_market_key(name), expected, "{}", name);
+    }
+}
+
+#[test]
+fn parses_pacifica_market_keys() {
+    let cases = [
+        ("ETH", key(1, "ETH")),
+        ("kBONK", key(1_000, "BONK")),
+        ("mMOG", key(1_000_000, "MOG")),
+        // Lowercase letters only scale when an uppercase asset follows
+        ("kaito", key(1, "kaito")),
+        ("KAITO", key(1, "KAITO")),
+    ];
+
+    for (symbol, expected) in cases {
+        assert_eq!(pacifica_market_key(symbol), expected, "{}", symbol);
+    }
+}
+
+#[test]
+fn computes_contract_ratios() {
+    let cases = [
+        ("ETH-USD", "ETH", 1.0),
+        ("1000BONK-USD", "kBONK", 1.0),
+        ("1000BONK-USD", "BONK", 0.001),
+        ("BONK-USD", "kBONK", 1_000.0),
+        ("1000000MOG-USD", "kMOG", 0.001),
+    ];
+
+    for (extended, pacifica, expected) in cases {
+        assert_eq!(
+            contract_ratio(extended, pacifica),
+            expected,
+            "{}/{}",
+            extended,
+            pacifica
+        );
+    }
+}
+
+#[test]
+fn matches_only_same_contract_size() {
+    let cases = [
+        (vec!["ETH-USD"], vec!["ETH"], vec![("ETH-USD", "ETH")]),
+        (
+            vec!["1000BONK-USD"],
+            vec!["kBONK"],
+            vec![("1000BONK-USD", "kBONK")],
+        ),
+        // Scaled on one venue and not on the other
+        (vec!["1000BONK-USD"], vec!["BONK"], vec![]),
+        (vec!["BONK-USD"], vec!["kBONK"], vec![]),
+        (vec!["1000PEPE-USD"], vec!["mPEPE"], vec![]),
+        (
+            vec!["ETH-USD", "SOL-USD", "1000PEPE-USD", "ETH-EUR"],
+            vec!["SOL", "kPEPE", "BTC"],
+            vec![("SOL-USD", "SOL"), ("1000PEPE-USD", "kPEPE")],
+        ),
+    ];
+
+    for (extended, pacifica, expected) in cases {
+        let expected = expected
+            .into_iter()
+            .map(|(extended_market, pacifica_market)| DiscoveredPair {
+                extended_market: extended_market.to_string(),
+                pacifica_market: pacifica_market.to_string(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            match_markets(&names(&extended), &names(&pacifica)),
+            expected,
+            "{:?} / {:?}",
+            extended,
+            pacifica
+        );
+    }
+}
@@ -0,0 +1,108 @@
+mod common;
+
+use funding_rate_bot::{
+    error::bot_error::BotError,
+    extended::orders::{
+        cancel_order::cancel_extended_order_by_external_id,
+        get_open_orders::get_extended_open_orders,
+    },
+    venue::{
+        perp_venue::PerpVenue,
+        structs::{Side, TpSl},
+    },
+};
+use serde_json::{Value, json};
+
+use common::mock_exchange::{EXTENDED_MARKET, MockExchange, Scenario};
+
+fn open_order(id: u64, market: &str, order_type: &str) -> Value {
+    json!({
+        "id": id,
+        "externalId": format!("external-{}", id),
+        "market": market,
+        "type": order_type,
+        "side": "SELL",
+        "status": "UNTRIGGERED",
+        "price": "2100",
+        "qty": "0.01",
+        "filledQty": "0",
+        "reduceOnly": true,
+        "postOnly": false,
+        "createdTime": 0,
+    })
+}
+
+#[tokio::test]
+async fn open_orders_are_listed_and_cancelled() {
+    let exchange = MockExchange::start(Scenario::default()).await;
+    exchange.state().extended_open_orders = vec![
+        open_order(1, EXTENDED_MARKET, "TPSL"),
+        open_order(2, EXTENDED_MARKET, "LIMIT"),
+        open_order(3, "BTC-USD", "TPSL"),
+    ];
+    let venue = exchange.extended_venue();
+
+    let orders = get_extended_open_orders(&venue.client, Some(EXTENDED_MARKET), &venue.api_key)
+        .await
+        .unwrap();
+    assert_eq!(orders.len(), 2);
+    assert_eq!(orders[0].r#type, "TPSL");
+    assert!(orders[0].reduce_only);
+
+    cancel_extended_order_by_external_id(&venue.client, "external-2", &venue.api_key)
+        .await
+        .unwrap();
+    assert_eq!(
+        exchange.state().extended_cancels,
+        vec!["external:external-2"]
+    );
+
+    venue.cancel_all_orders(EXTENDED_MARKET).await.unwrap();
+    let state = exchange.state();
+    assert_eq!(
+        state.extended_mass_cancels[0]["markets"],
+        json!([EXTENDED_MARKET])
+    );
+    assert_eq!(state.extended_open_orders.len(), 1);
+    assert_eq!(state.extended_open_orders[0]["market"], "BTC-USD");
+}
+
+#[tokio::test]
+async fn unknown_market_is_an_error() {
+    let exchange = MockExchange::start(Scenario::default()).await;
+    let venue = exchange.extended_venue();
+
+    let error = venue.get_market_data("DELISTED-USD").await.unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<BotError>(),
+        Some(BotError::Rejected { .. })
+    ));
+}
+
+#[tokio::test]
+async fn places_a_standalone_tp_sl() {
+    let exchange = MockExchange::start(Scenario::default()).await;
+    let venue = exchange.extended_venue();
+    let market = venue.get_market_data(EXTENDED_MARKET).await.unwrap();
+    let tp_sl = TpSl {
+        take_profit: 0.05,
+        stop_loss: 0.05,
+        limit_offset: 0.005,
+    };
+
+    venue
+        .place_tp_sl(EXTENDED_MARKET, &market, Side::Sell, 0.012, tp_sl)
+        .await
+        .unwrap();
+
+    let state = exchange.state();
+    let order = &state.extended_orders[0];
+    // Closing a short: buys below the entry for profit, above it to stop out
+    assert_eq!(order["type"], "TPSL");
+    assert_eq!(order["side"], "BUY");
+    assert_eq!(order["qty"], "0.012");
+    assert_eq!(order["reduceOnly"], true);
+    assert_eq!(order["takeProfit"]["triggerPrice"], "1900");
+    assert_eq!(order["stopLoss"]["triggerPrice"], "2100");
+    assert!(state.extended_positions.is_empty());
+}
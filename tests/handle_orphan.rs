@@ -0,0 +1,196 @@
+mod common;
+
+use funding_rate_bot::{
+    config::structs::OrphanPolicy,
+    strategy::{handle_orphan::handle_orphan, structs::VenueLeg},
+    venue::perp_venue::PerpVenue,
+};
+
+use common::mock_exchange::{EXTENDED_MARKET, MockExchange, PACIFICA_MARKET, Scenario, eth_pair};
+
+/// Short on Extended without the Pacifica long.
+async fn extended_orphan(scenario: Scenario) -> MockExchange {
+    let exchange = MockExchange::start(scenario).await;
+    exchange
+        .state()
+        .extended_positions
+        .insert(EXTENDED_MARKET.to_string(), -0.012);
+    exchange
+}
+
+/// Long on Pacifica without the Extended short.
+async fn pacifica_orphan(scenario: Scenario) -> MockExchange {
+    let exchange = MockExchange::start(scenario).await;
+    exchange
+        .state()
+        .pacifica_positions
+        .insert(PACIFICA_MARKET.to_string(), 0.012);
+    exchange
+}
+
+async fn run_extended(exchange: &MockExchange, policy: OrphanPolicy) -> anyhow::Result<()> {
+    let pair = eth_pair();
+    let extended = exchange.extended_venue();
+    let pacifica = exchange.pacifica_venue();
+    let position = extended.get_open_positions().await?.remove(0);
+
+    handle_orphan(
+        VenueLeg {
+            venue: &extended,
+            leg: &pair.extended,
+        },
+        &position,
+        VenueLeg {
+            venue: &pacifica,
+            leg: &pair.pacifica,
+        },
+        1.0,
+        policy,
+        &pair.tp_sl,
+    )
+    .await
+}
+
+async fn run_pacifica(exchange: &MockExchange, policy: OrphanPolicy) -> anyhow::Result<()> {
+    let pair = eth_pair();
+    let extended = exchange.extended_venue();
+    let pacifica = exchange.pacifica_venue();
+    let position = pacifica.get_open_positions().await?.remove(0);
+
+    handle_orphan(
+        VenueLeg {
+            venue: &pacifica,
+            leg: &pair.pacifica,
+        },
+        &position,
+        VenueLeg {
+            venue: &extended,
+            leg: &pair.extended,
+        },
+        1.0,
+        policy,
+        &pair.tp_sl,
+    )
+    .await
+}
+
+#[tokio::test]
+async fn closes_an_extended_orphan() {
+    let exchange = extended_orphan(Scenario::default()).await;
+
+    run_extended(&exchange, OrphanPolicy::Close).await.unwrap();
+
+    let state = exchange.state();
+    assert_eq!(state.extended_orders[0]["side"], "BUY");
+    assert_eq!(state.extended_orders[0]["reduceOnly"], true);
+    assert_eq!(state.extended_positions[EXTENDED_MARKET], 0.0);
+    assert_eq!(state.extended_mass_cancels.len(), 1);
+    assert_eq!(state.pacifica_cancels.len(), 1);
+    assert!(state.pacifica_orders.is_empty());
+}
+
+#[tokio::test]
+async fn closes_a_pacifica_orphan() {
+    let exchange = pacifica_orphan(Scenario::default()).await;
+
+    run_pacifica(&exchange, OrphanPolicy::Close).await.unwrap();
+
+    let state = exchange.state();
+    assert_eq!(state.pacifica_orders[0]["side"], "ask");
+    assert_eq!(state.pacifica_orders[0]["reduce_only"], true);
+    assert_eq!(state.pacifica_positions[PACIFICA_MARKET], 0.0);
+    assert_eq!(state.pacifica_cancels.len(), 1);
+    assert_eq!(state.extended_mass_cancels.len(), 1);
+    assert!(state.extended_orders.is_empty());
+}
+
+#[tokio::test]
+async fn rehedges_an_extended_orphan() {
+    let exchange = extended_orphan(Scenario::default()).await;
+
+    run_extended(&exchange, OrphanPolicy::Rehedge)
+        .await
+        .unwrap();
+
+    let state = exchange.state();
+    assert_eq!(state.pacifica_cancels.len(), 1);
+    assert_eq!(state.pacifica_orders[0]["side"], "bid");
+    assert!((state.pacifica_positions[PACIFICA_MARKET] - 0.012).abs() < 1e-9);
+    assert!(state.extended_orders.is_empty());
+}
+
+#[tokio::test]
+async fn rehedges_a_pacifica_orphan() {
+    let exchange = pacifica_orphan(Scenario::default()).await;
+
+    run_pacifica(&exchange, OrphanPolicy::Rehedge)
+        .await
+        .unwrap();
+
+    let state = exchange.state();
+    assert_eq!(state.extended_mass_cancels.len(), 1);
+    assert_eq!(state.extended_orders[0]["side"], "SELL");
+    assert!((state.extended_positions[EXTENDED_MARKET] + 0.012).abs() < 1e-9);
+    assert!(state.pacifica_orders.is_empty());
+}
+
+#[tokio::test]
+async fn alerts_on_an_extended_orphan() {
+    let exchange = extended_orphan(Scenario::default()).await;
+
+    assert!(run_extended(&exchange, OrphanPolicy::Alert).await.is_err());
+
+    let state = exchange.state();
+    assert!(state.extended_orders.is_empty());
+    assert!(state.pacifica_orders.is_empty());
+    assert!(state.extended_mass_cancels.is_empty());
+    assert!(state.pacifica_cancels.is_empty());
+}
+
+#[tokio::test]
+async fn alerts_on_a_pacifica_orphan() {
+    let exchange = pacifica_orphan(Scenario::default()).await;
+
+    assert!(run_pacifica(&exchange, OrphanPolicy::Alert).await.is_err());
+
+    let state = exchange.state();
+    assert!(state.extended_orders.is_empty());
+    assert!(state.pacifica_orders.is_empty());
+    assert!(state.extended_mass_cancels.is_empty());
+    assert!(state.pacifica_cancels.is_empty());
+}
+
+#[tokio::test]
+async fn close_cancels_the_hedge_venue_when_the_orphan_cancel_fails() {
+    let exchange = extended_orphan(Scenario {
+        reject_extended_cancels: true,
+        ..Scenario::default()
+    })
+    .await;
+
+    let error = run_extended(&exchange, OrphanPolicy::Close)
+        .await
+        .unwrap_err();
+
+    assert!(format!("{:#}", error).contains("Mass cancel failed"));
+    let state = exchange.state();
+    assert_eq!(state.extended_positions[EXTENDED_MARKET], 0.0);
+    assert_eq!(state.pacifica_cancels.len(), 1);
+}
+
+#[tokio::test]
+async fn rehedge_opens_the_leg_when_the_cancel_fails() {
+    let exchange = extended_orphan(Scenario {
+        reject_pacifica_cancels: true,
+        ..Scenario::default()
+    })
+    .await;
+
+    let error = run_extended(&exchange, OrphanPolicy::Rehedge)
+        .await
+        .unwrap_err();
+
+    assert!(format!("{:#}", error).contains("Cancel all failed"));
+    let state = exchange.state();
+    assert!((state.pacifica_positions[PACIFICA_MARKET] - 0.012).abs() < 1e-9);
+}
@@ -0,0 +1,144 @@
+use funding_rate_bot::config::{load_config::load_config, structs::Config};
+
+const DEFAULTS: &str = r#"
+target_minute = 28
+
+[defaults]
+buy_amount = 25.0
+funding_rate_threshold = 0.001
+price_spread_threshold = 0.02
+extended_slippage = 0.01
+pacifica_slippage = 0.0001
+
+[defaults.exit]
+min_funding_periods = 2
+"#;
+
+/// Writes `contents` to a file of its own in the temp directory and loads it.
+fn load(name: &str, contents: &str) -> anyhow::Result<Config> {
+    let path = std::env::temp_dir().join(format!(
+        "funding_rate_bot_{}_{}.toml",
+        name,
+        std::process::id()
+    ));
+    std::fs::write(&path, contents).unwrap();
+    let config = load_config(path.to_str().unwrap());
+    std::fs::remove_file(&path).unwrap();
+    config
+}
+
+#[test]
+fn parses_minimal_config() {
+    let config = load(
+        "minimal",
+        &format!(
+            "{}\n[[pairs]]\nextended = \"ETH-USD\"\npacifica = \"ETH\"\n",
+            DEFAULTS
+        ),
+    )
+    .unwrap();
+
+    assert_eq!(config.target_minute, 28);
+    assert_eq!(config.pairs.len(), 1);
+    let pair = &config.pairs[0];
+    assert_eq!(pair.extended.market, "ETH-USD");
+    assert_eq!(pair.pacifica.market, "ETH");
+    assert_eq!(pair.buy_amount, 25.0);
+    assert_eq!(pair.extended.slippage, 0.01);
+    assert_eq!(pair.exit.min_funding_periods, 2);
+    assert_eq!(pair.sizing.leverage, 1.0);
+    assert_eq!(config.allocation.max_new_pairs, 3);
+    assert_eq!(config.allocation.max_margin_utilization, 1.0);
+    assert_eq!(config.fees.pacifica_maker_fee, 0.00015);
+    assert_eq!(config.fees.pacifica_taker_fee, 0.0004);
+}
+
+#[test]
+fn applies_pair_overrides() {
+    let config = load(
+        "overrides",
+        &format!(
+            r#"{}
+[[pairs]]
+extended = "ETH-USD"
+pacifica = "ETH"
+
+[[pairs]]
+extended = "SOL-USD"
+pacifica = "SOL"
+buy_amount = 50.0
+pacifica_slippage = 0.002
+exit = {{ basis_take_profit = 0.5 }}
+sizing = {{ leverage = 3.0 }}
+tp_sl = {{ enabled = false }}
+"#,
+            DEFAULTS
+        ),
+    )
+    .unwrap();
+
+    let (eth, sol) = (&config.pairs[0], &config.pairs[1]);
+    assert_eq!(eth.buy_amount, 25.0);
+    assert!(eth.tp_sl.enabled);
+
+    assert_eq!(sol.buy_amount, 50.0);
+    assert_eq!(sol.pacifica.slippage, 0.002);
+    assert_eq!(sol.extended.slippage, 0.01);
+    // Sub-tables only replace the keys they set
+    assert_eq!(sol.exit.basis_take_profit, 0.5);
+    assert_eq!(sol.exit.min_funding_periods, 2);
+    assert_eq!(sol.sizing.leverage, 3.0);
+    assert_eq!(sol.sizing.collateral_usage, eth.sizing.collateral_usage);
+    assert!(!sol.tp_sl.enabled);
+    assert_eq!(sol.tp_sl.take_profit, eth.tp_sl.take_profit);
+}
+
+#[test]
+fn rejects_invalid_values() {
+    let cases = [
+        (
+            "duplicate",
+            "[[pairs]]\nextended = \"ETH-USD\"\npacifica = \"ETH\"\n\n\
+             [[pairs]]\nextended = \"ETH-USD\"\npacifica = \"kETH\"\n",
+            "configured more than once",
+        ),
+        (
+            "exit_threshold",
+            "[[pairs]]\nextended = \"ETH-USD\"\npacifica = \"ETH\"\n\
+             exit = { exit_threshold = 0.002 }\n",
+            "exit_threshold must not be above",
+        ),
+        (
+            "utilization",
+            "[allocation]\nmax_margin_utilization = 0.0\n\n\
+             [[pairs]]\nextended = \"ETH-USD\"\npacifica = \"ETH\"\n",
+            "max_margin_utilization must be above 0",
+        ),
+        (
+            "contract_size",
+            "[[pairs]]\nextended = \"1000BONK-USD\"\npacifica = \"BONK\"\n",
+            "trades contracts of different sizes",
+        ),
+        (
+            "fee",
+            "[fees]\npacifica_taker_fee = 1.5\n\n\
+             [[pairs]]\nextended = \"ETH-USD\"\npacifica = \"ETH\"\n",
+            "Fee pacifica_taker_fee must be between 0 and 1",
+        ),
+        (
+            "unknown_field",
+            "[[pairs]]\nextended = \"ETH-USD\"\npacifica = \"ETH\"\nbuy_ammount = 10.0\n",
+            "unknown field",
+        ),
+    ];
+
+    for (name, pairs, message) in cases {
+        let error = load(name, &format!("{}\n{}", DEFAULTS, pairs)).unwrap_err();
+        assert!(
+            error.to_string().contains(message),
+            "{}: unexpected error {}",
+            name,
+            error
+        );
+    }
+}
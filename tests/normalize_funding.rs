@@ -0,0 +1,85 @@
+mod common;
+
+use funding_rate_bot::{
+    funding::normalize_funding::normalize_funding,
+    venue::{perp_venue::PerpVenue, structs::MarketSnapshot},
+};
+
+use common::mock_exchange::{EXTENDED_MARKET, MockExchange, PACIFICA_MARKET, Scenario};
+
+fn snapshot(funding_rate: f64, funding_interval_hours: f64, next: Option<u64>) -> MarketSnapshot {
+    MarketSnapshot {
+        market: String::from("ETH"),
+        bid: 2000.0,
+        ask: 2000.0,
+        mark: 2000.0,
+        funding_rate,
+        funding_interval_hours,
+        max_funding_rate: None,
+        next_funding_time: next,
+        lot_size: 0.001,
+        max_order_value: None,
+        max_position_value: None,
+    }
+}
+
+#[test]
+fn rates_paid_at_different_intervals_compare_per_hour() {
+    let hourly = normalize_funding(&snapshot(0.0001, 1.0, None), 0);
+    let eight_hourly = normalize_funding(&snapshot(0.0008, 8.0, None), 0);
+
+    assert!((hourly.hourly_rate - eight_hourly.hourly_rate).abs() < 1e-12);
+    assert!((hourly.apr - 87.6).abs() < 1e-9);
+    assert!((eight_hourly.apr - 87.6).abs() < 1e-9);
+}
+
+#[test]
+fn next_payment_falls_back_to_the_interval_boundary() {
+    let now = 1_700_000_000_000;
+    let published = normalize_funding(&snapshot(0.0001, 1.0, Some(now + 42)), now);
+    let hourly = normalize_funding(&snapshot(0.0001, 1.0, None), now);
+    let eight_hourly = normalize_funding(&snapshot(0.0001, 8.0, None), now);
+
+    assert_eq!(published.next_payment, now + 42);
+    assert_eq!(hourly.next_payment, 1_700_002_800_000);
+    assert_eq!(eight_hourly.next_payment, 1_700_006_400_000);
+}
+
+#[test]
+fn rates_past_the_cap_are_clamped() {
+    let mut capped = snapshot(0.01, 1.0, None);
+    capped.max_funding_rate = Some(0.001);
+    let positive = normalize_funding(&capped, 0);
+    capped.funding_rate = -0.01;
+    let negative = normalize_funding(&capped, 0);
+
+    assert!((positive.hourly_rate - 0.001).abs() < 1e-12);
+    assert!((negative.hourly_rate + 0.001).abs() < 1e-12);
+}
+
+#[tokio::test]
+async fn venues_publish_the_projected_rate_interval_and_cap() {
+    let exchange = MockExchange::start(Scenario {
+        extended_funding_rate: 0.0008,
+        extended_funding_interval_hours: 8.0,
+        max_funding_rate: Some(0.005),
+        ..Scenario::default()
+    })
+    .await;
+    let extended = exchange.extended_venue();
+    let pacifica = exchange.pacifica_venue();
+
+    let extended_snapshot = extended
+        .market_snapshot(&extended.get_market_data(EXTENDED_MARKET).await.unwrap())
+        .unwrap();
+    let pacifica_snapshot = pacifica
+        .market_snapshot(&pacifica.get_market_data(PACIFICA_MARKET).await.unwrap())
+        .unwrap();
+
+    assert_eq!(extended_snapshot.funding_rate, 0.0008);
+    assert_eq!(extended_snapshot.funding_interval_hours, 8.0);
+    assert_eq!(extended_snapshot.max_funding_rate, Some(0.005));
+    assert_eq!(extended_snapshot.next_funding_time, None);
+    assert_eq!(pacifica_snapshot.funding_interval_hours, 1.0);
+    assert_eq!(pacifica_snapshot.max_funding_rate, Some(0.005));
+}
@@ -0,0 +1,125 @@
+mod common;
+
+use funding_rate_bot::{
+    pacifica::{
+        orders::{
+            cancel_all_orders::cancel_all_pacifica_orders,
+            stop_orders::{cancel_pacifica_stop_order, create_pacifica_stop_order},
+        },
+        structs::Side as PacificaSide,
+    },
+    venue::{
+        perp_venue::PerpVenue,
+        structs::{Side, TpSl},
+    },
+};
+use rust_decimal_macros::dec;
+
+use common::mock_exchange::{MockExchange, PACIFICA_MARKET, Scenario};
+
+#[tokio::test]
+async fn limit_order_is_post_only_and_rounded_away_from_the_book() {
+    let exchange = MockExchange::start(Scenario::default()).await;
+    let venue = exchange.pacifica_venue();
+    let market = venue.get_market_data(PACIFICA_MARKET).await.unwrap();
+
+    let ack = venue
+        .place_limit_order(PACIFICA_MARKET, &market, Side::Buy, 0.01234, 1999.97, None)
+        .await
+        .unwrap();
+    assert_eq!(ack.venue_order_id.as_deref(), Some("1"));
+
+    let state = exchange.state();
+    let order = &state.pacifica_orders[0];
+    assert_eq!(order["tif"], "ALO");
+    assert_eq!(order["side"], "bid");
+    assert_eq!(order["price"], "1999.9");
+    assert_eq!(order["amount"], "0.0123");
+    assert_eq!(order["reduce_only"], false);
+}
+
+#[tokio::test]
+async fn limit_order_takes_tp_sl_from_its_price() {
+    let exchange = MockExchange::start(Scenario::default()).await;
+    let venue = exchange.pacifica_venue();
+    let market = venue.get_market_data(PACIFICA_MARKET).await.unwrap();
+
+    let tp_sl = TpSl {
+        take_profit: 0.05,
+        stop_loss: 0.05,
+        limit_offset: 0.005,
+    };
+    venue
+        .place_limit_order(
+            PACIFICA_MARKET,
+            &market,
+            Side::Sell,
+            0.01,
+            2010.0,
+            Some(tp_sl),
+        )
+        .await
+        .unwrap();
+
+    // A short takes profit below the limit price and stops out above it
+    let state = exchange.state();
+    let order = &state.pacifica_orders[0];
+    assert_eq!(order["tif"], "ALO");
+    assert_eq!(order["take_profit"]["stop_price"], "1909.5");
+    assert_eq!(order["take_profit"]["limit_price"], "1919.6");
+    assert_eq!(order["stop_loss"]["stop_price"], "2110.5");
+    assert_eq!(order["reduce_only"], false);
+}
+
+#[tokio::test]
+async fn stale_orders_are_cancelled() {
+    let exchange = MockExchange::start(Scenario::default()).await;
+    let venue = exchange.pacifica_venue();
+    let market = venue.get_market_data(PACIFICA_MARKET).await.unwrap();
+
+    venue
+        .place_limit_order(PACIFICA_MARKET, &market, Side::Sell, 0.01, 2010.0, None)
+        .await
+        .unwrap();
+    let stop_ack = create_pacifica_stop_order(
+        &venue.client,
+        PACIFICA_MARKET,
+        PacificaSide::Ask,
+        dec!(0.01),
+        dec!(1900),
+        None,
+        true,
+        &market,
+        &venue.private_key,
+        &venue.wallet_address,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        exchange.state().pacifica_orders[1]["stop_order"]["stop_price"],
+        "1900"
+    );
+
+    cancel_pacifica_stop_order(
+        &venue.client,
+        PACIFICA_MARKET,
+        stop_ack.venue_order_id.as_deref().unwrap(),
+        &venue.private_key,
+        &venue.wallet_address,
+    )
+    .await
+    .unwrap();
+    assert_eq!(exchange.state().pacifica_cancels[0]["order_id"], 2);
+
+    let cancelled = cancel_all_pacifica_orders(
+        &venue.client,
+        Some(PACIFICA_MARKET),
+        false,
+        &venue.private_key,
+        &venue.wallet_address,
+    )
+    .await
+    .unwrap();
+    assert!(cancelled > 0);
+    assert!(exchange.state().pacifica_open_orders.is_empty());
+}
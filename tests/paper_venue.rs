@@ -0,0 +1,81 @@
+mod common;
+
+use funding_rate_bot::{
+    extended::venue::ExtendedVenue,
+    strategy::{place_arb_order::evaluate_pair, position_size::position_size},
+    venue::{
+        paper_venue::PaperVenue,
+        perp_venue::PerpVenue,
+        structs::{FeeRates, OrderIntent, Side},
+    },
+};
+
+use common::mock_exchange::{EXTENDED_MARKET, MockExchange, Scenario, eth_pair};
+
+#[tokio::test]
+async fn evaluates_pairs_without_credentials() {
+    let exchange = MockExchange::start(Scenario::default()).await;
+    let pair = eth_pair();
+
+    // Dry runs start without any API key
+    let extended = ExtendedVenue {
+        api_key: String::new(),
+        ..exchange.extended_venue()
+    };
+    assert!(extended.get_fees(EXTENDED_MARKET).await.is_err());
+
+    let fees = FeeRates {
+        maker: 0.0,
+        taker: 0.00025,
+    };
+    let extended = PaperVenue::new(extended, 1000.0, 1.0, fees);
+    let pacifica = PaperVenue::new(exchange.pacifica_venue(), 1000.0, 1.0, fees);
+    assert_eq!(extended.get_fees(EXTENDED_MARKET).await.unwrap(), fees);
+
+    let opportunity = evaluate_pair(&extended, &pair.extended, &pacifica, &pair.pacifica, &pair)
+        .await
+        .unwrap();
+    assert!(opportunity.net_return > 0.0);
+    assert!(exchange.state().extended_orders.is_empty());
+}
+
+#[tokio::test]
+async fn charges_fees_and_rests_limit_orders_until_crossed() {
+    let exchange = MockExchange::start(Scenario::default()).await;
+    let fees = FeeRates {
+        maker: 0.0001,
+        taker: 0.0005,
+    };
+    let venue = PaperVenue::new(exchange.extended_venue(), 1000.0, 1.0, fees);
+    let market = venue.get_market_data(EXTENDED_MARKET).await.unwrap();
+
+    // Market buy at the 2000 ask pays the taker fee
+    venue
+        .place_order(
+            EXTENDED_MARKET,
+            &market,
+            Side::Buy,
+            0.1,
+            0.0,
+            OrderIntent::Open(None),
+        )
+        .await
+        .unwrap();
+    let balance = venue.get_tradeable_balance().await.unwrap().balance;
+    assert!((balance - (1000.0 - 0.1 * 2000.0 * 0.0005)).abs() < 1e-9);
+
+    // A bid below the book rests until the price trades down to it
+    let ack = venue
+        .place_limit_order(EXTENDED_MARKET, &market, Side::Buy, 0.1, 1990.0, None)
+        .await
+        .unwrap();
+    assert!(ack.venue_order_id.is_some());
+    let size = position_size(&venue, EXTENDED_MARKET).await.unwrap();
+    assert_eq!(size, 0.1);
+
+    exchange.state().scenario.extended_price = 1980.0;
+    let size = position_size(&venue, EXTENDED_MARKET).await.unwrap();
+    assert!((size - 0.2).abs() < 1e-9);
+    let balance_after = venue.get_tradeable_balance().await.unwrap().balance;
+    assert!((balance - balance_after - 0.1 * 1990.0 * 0.0001).abs() < 1e-9);
+}
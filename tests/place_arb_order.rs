@@ -0,0 +1,615 @@
+mod common;
+
+use std::time::Duration;
+
+use anyhow::anyhow;
+
+use funding_rate_bot::{
+    config::structs::{AllocationConfig, EntryMode, MarketPair, SizingPolicy, TpSlMode},
+    error::bot_error::{BotError, SkipReason},
+    portfolio::{check_limits::check_limits, load_portfolio::load_portfolio},
+    strategy::{
+        place_arb_order::{evaluate_pair, open_pair},
+        plan_entries::plan_entries,
+    },
+};
+
+use common::mock_exchange::{
+    EXTENDED_MARKET, MockExchange, OrderBehavior, PACIFICA_MARKET, Scenario, eth_pair,
+};
+
+async fn run(exchange: &MockExchange) -> anyhow::Result<()> {
+    run_pair(exchange, eth_pair()).await
+}
+
+async fn run_pair(exchange: &MockExchange, pair: MarketPair) -> anyhow::Result<()> {
+    run_with_allocation(exchange, pair, &AllocationConfig::default()).await
+}
+
+/// One entry cycle of the bot for `pair`: evaluated, planned against the
+/// portfolio and the `allocation` caps, then opened. A pair the plan leaves
+/// out is an error, so it can be told apart from an opened one.
+async fn run_with_allocation(
+    exchange: &MockExchange,
+    pair: MarketPair,
+    allocation: &AllocationConfig,
+) -> anyhow::Result<()> {
+    let extended = exchange.extended_venue();
+    let pacifica = exchange.pacifica_venue();
+    let portfolio = load_portfolio(&extended, &pacifica).await?;
+    let opportunity =
+        evaluate_pair(&extended, &pair.extended, &pacifica, &pair.pacifica, &pair).await?;
+
+    let entry = plan_entries(vec![(&pair, opportunity)], &portfolio, allocation)
+        .pop()
+        .ok_or_else(|| anyhow!("{} was left out of the plan", pair.extended.market))?;
+
+    open_pair(
+        &extended,
+        &pair.extended,
+        &pacifica,
+        &pair.pacifica,
+        &pair,
+        &entry.opportunity,
+        entry.size,
+    )
+    .await
+}
+
+/// Why the allocation caps keep `pair` out of the plan, if they do.
+async fn limit_error(
+    exchange: &MockExchange,
+    pair: &MarketPair,
+    allocation: &AllocationConfig,
+) -> anyhow::Error {
+    let portfolio = load_portfolio(&exchange.extended_venue(), &exchange.pacifica_venue())
+        .await
+        .unwrap();
+    check_limits(&portfolio, pair, &pair.extended, &pair.pacifica, allocation).unwrap_err()
+}
+
+fn bot_error(error: &anyhow::Error) -> &BotError {
+    error
+        .downcast_ref::<BotError>()
+        .unwrap_or_else(|| panic!("expected a BotError, got {:?}", error))
+}
+
+#[tokio::test]
+async fn opens_both_legs() {
+    let exchange = MockExchange::start(Scenario::default()).await;
+
+    run(&exchange).await.unwrap();
+
+    let state = exchange.state();
+    assert_eq!(state.extended_orders.len(), 1);
+    assert_eq!(state.pacifica_orders.len(), 1);
+    // Extended pays the higher funding rate, so it is the short leg
+    assert_eq!(state.extended_orders[0]["side"], "SELL");
+    assert_eq!(state.pacifica_orders[0]["side"], "bid");
+    assert!(state.extended_positions[EXTENDED_MARKET] < 0.0);
+    assert!(state.pacifica_positions[PACIFICA_MARKET] > 0.0);
+}
+
+#[tokio::test]
+async fn unwinds_first_leg_when_hedge_is_rejected() {
+    let exchange = MockExchange::start(Scenario {
+        pacifica_order: OrderBehavior::Reject,
+        ..Scenario::default()
+    })
+    .await;
+
+    let error = run(&exchange).await.unwrap_err();
+    assert!(matches!(
+        bot_error(&error),
+        BotError::Rejected { body: Some(_), .. }
+    ));
+
+    let state = exchange.state();
+    assert_eq!(state.extended_orders.len(), 2);
+    assert_eq!(state.extended_orders[1]["side"], "BUY");
+    assert!(state.extended_positions[EXTENDED_MARKET].abs() < 1e-9);
+    assert!(state.pacifica_positions.is_empty());
+}
+
+#[tokio::test]
+async fn unwinds_hedge_leg_when_first_leg_is_rejected() {
+    let exchange = MockExchange::start(Scenario {
+        extended_order: OrderBehavior::Reject,
+        ..Scenario::default()
+    })
+    .await;
+
+    let error = run(&exchange).await.unwrap_err();
+    assert!(matches!(bot_error(&error), BotError::Rejected { .. }));
+
+    let state = exchange.state();
+    assert_eq!(state.pacifica_orders.len(), 2);
+    assert_eq!(state.pacifica_orders[0]["side"], "bid");
+    assert_eq!(state.pacifica_orders[1]["side"], "ask");
+    assert!(state.extended_positions.is_empty());
+    assert!(state.pacifica_positions[PACIFICA_MARKET].abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn unwinds_only_the_filled_part_of_a_leg() {
+    let exchange = MockExchange::start(Scenario {
+        extended_order: OrderBehavior::PartialFill(0.5),
+        pacifica_order: OrderBehavior::Reject,
+        ..Scenario::default()
+    })
+    .await;
+
+    run(&exchange).await.unwrap_err();
+
+    let state = exchange.state();
+    let entry_qty = state.extended_orders[0]["qty"]
+        .as_str()
+        .unwrap()
+        .parse::<f64>()
+        .unwrap();
+    let unwind_qty = state.extended_orders[1]["qty"]
+        .as_str()
+        .unwrap()
+        .parse::<f64>()
+        .unwrap();
+    // The half that filled is unwound, not the size the entry asked for
+    assert_eq!(state.extended_orders[1]["reduceOnly"], true);
+    assert!((unwind_qty - entry_qty / 2.0).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn keeps_both_errors_when_unwind_fails() {
+    let exchange = MockExchange::start(Scenario {
+        extended_order: OrderBehavior::RejectReduceOnly,
+        pacifica_order: OrderBehavior::Reject,
+        ..Scenario::default()
+    })
+    .await;
+
+    let error = run(&exchange).await.unwrap_err();
+    let message = format!("{:#}", error);
+    assert!(
+        message.contains("to unwind the Extended leg"),
+        "{}",
+        message
+    );
+    // The Pacifica rejection and the failed Extended unwind
+    assert!(message.contains("code 400"), "{}", message);
+    assert!(message.contains("code 1140"), "{}", message);
+    assert!(matches!(bot_error(&error), BotError::Rejected { .. }));
+
+    let state = exchange.state();
+    assert_eq!(state.extended_orders.len(), 2);
+    assert!(state.extended_positions[EXTENDED_MARKET] < 0.0);
+}
+
+#[tokio::test]
+async fn skips_when_price_spread_is_too_high() {
+    let exchange = MockExchange::start(Scenario {
+        pacifica_price: 2100.0,
+        ..Scenario::default()
+    })
+    .await;
+
+    let error = run(&exchange).await.unwrap_err();
+    assert!(matches!(
+        bot_error(&error),
+        BotError::Skip(SkipReason::PriceSpreadTooHigh { .. })
+    ));
+
+    let state = exchange.state();
+    assert!(state.extended_orders.is_empty());
+    assert!(state.pacifica_orders.is_empty());
+}
+
+#[tokio::test]
+async fn skips_when_funding_rates_are_close() {
+    let exchange = MockExchange::start(Scenario {
+        pacifica_funding_rate: 0.0001,
+        ..Scenario::default()
+    })
+    .await;
+
+    let error = run(&exchange).await.unwrap_err();
+    assert!(matches!(
+        bot_error(&error),
+        BotError::Skip(SkipReason::FundingRateDiffTooLow { .. })
+    ));
+    assert!(exchange.state().extended_orders.is_empty());
+}
+
+#[tokio::test]
+async fn skips_when_fees_eat_the_funding() {
+    // 0.002%/h passes the threshold but earns less than the round trip costs
+    let exchange = MockExchange::start(Scenario {
+        extended_funding_rate: 0.00003,
+        ..Scenario::default()
+    })
+    .await;
+
+    let error = run(&exchange).await.unwrap_err();
+    assert!(matches!(
+        bot_error(&error),
+        BotError::Skip(SkipReason::NetEdgeTooLow { .. })
+    ));
+    assert!(exchange.state().extended_orders.is_empty());
+
+    // A longer expected hold earns enough funding to cover the costs
+    run_pair(
+        &exchange,
+        MarketPair {
+            expected_holding_hours: 240.0,
+            ..eth_pair()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(exchange.state().extended_orders.len(), 1);
+}
+
+#[tokio::test]
+async fn sizes_down_to_available_collateral() {
+    let exchange = MockExchange::start(Scenario {
+        balance: 10.0,
+        ..Scenario::default()
+    })
+    .await;
+
+    // 90% of 10 USD at 2x leverage
+    run_pair(
+        &exchange,
+        MarketPair {
+            sizing: SizingPolicy {
+                leverage: 2.0,
+                ..SizingPolicy::default()
+            },
+            ..eth_pair()
+        },
+    )
+    .await
+    .unwrap();
+
+    let state = exchange.state();
+    assert!((state.extended_positions[EXTENDED_MARKET] + 0.009).abs() < 1e-9);
+    assert!((state.pacifica_positions[PACIFICA_MARKET] - 0.009).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn sizes_by_share_of_equity() {
+    let exchange = MockExchange::start(Scenario::default()).await;
+
+    // 10% of 1000 USD at 3x leverage
+    run_pair(
+        &exchange,
+        MarketPair {
+            sizing: SizingPolicy {
+                leverage: 3.0,
+                equity_fraction: 0.1,
+                ..SizingPolicy::default()
+            },
+            ..eth_pair()
+        },
+    )
+    .await
+    .unwrap();
+
+    let state = exchange.state();
+    assert!((state.extended_positions[EXTENDED_MARKET] + 0.15).abs() < 1e-9);
+    assert!((state.pacifica_positions[PACIFICA_MARKET] - 0.15).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn skips_when_collateral_is_below_one_lot() {
+    let exchange = MockExchange::start(Scenario {
+        balance: 1.0,
+        ..Scenario::default()
+    })
+    .await;
+
+    let error = run(&exchange).await.unwrap_err();
+    assert!(error.to_string().contains("left out of the plan"));
+    assert!(exchange.state().extended_orders.is_empty());
+}
+
+#[tokio::test]
+async fn never_adds_to_a_held_pair() {
+    let exchange = MockExchange::start(Scenario::default()).await;
+    run(&exchange).await.unwrap();
+
+    run(&exchange).await.unwrap_err();
+    let error = limit_error(&exchange, &eth_pair(), &AllocationConfig::default()).await;
+    assert!(matches!(
+        bot_error(&error),
+        BotError::Skip(SkipReason::AlreadyHeld { .. })
+    ));
+    assert_eq!(exchange.state().extended_orders.len(), 1);
+    assert_eq!(exchange.state().pacifica_orders.len(), 1);
+}
+
+#[tokio::test]
+async fn skips_when_max_open_pairs_are_held() {
+    let exchange = MockExchange::start(Scenario::default()).await;
+    exchange
+        .state()
+        .extended_positions
+        .insert(String::from("BTC-USD"), -0.001);
+
+    let allocation = AllocationConfig {
+        max_open_pairs: 1,
+        ..AllocationConfig::default()
+    };
+    run_with_allocation(&exchange, eth_pair(), &allocation)
+        .await
+        .unwrap_err();
+    let error = limit_error(&exchange, &eth_pair(), &allocation).await;
+    assert!(matches!(
+        bot_error(&error),
+        BotError::Skip(SkipReason::MaxOpenPairs { open: 1, limit: 1 })
+    ));
+    assert!(exchange.state().extended_orders.is_empty());
+}
+
+#[tokio::test]
+async fn caps_size_by_total_exposure_and_margin() {
+    let exchange = MockExchange::start(Scenario::default()).await;
+
+    // 10 USD of notional left on each venue
+    let allocation = AllocationConfig {
+        max_total_exposure: 10.0,
+        ..AllocationConfig::default()
+    };
+    run_with_allocation(&exchange, eth_pair(), &allocation)
+        .await
+        .unwrap();
+    assert!((exchange.state().extended_positions[EXTENDED_MARKET] + 0.005).abs() < 1e-9);
+
+    // 1% of the 1000 USD equity as margin
+    let exchange = MockExchange::start(Scenario::default()).await;
+    let allocation = AllocationConfig {
+        max_margin_utilization: 0.01,
+        ..AllocationConfig::default()
+    };
+    run_with_allocation(&exchange, eth_pair(), &allocation)
+        .await
+        .unwrap();
+    assert!((exchange.state().pacifica_positions[PACIFICA_MARKET] - 0.005).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn slow_venue_times_out_as_retryable() {
+    let exchange = MockExchange::start(Scenario {
+        response_delay: Duration::from_secs(2),
+        ..Scenario::default()
+    })
+    .await;
+
+    let error = run(&exchange).await.unwrap_err();
+    assert!(bot_error(&error).is_retryable());
+}
+
+#[tokio::test]
+async fn partial_fill_is_reconciled_on_short_leg() {
+    let exchange = MockExchange::start(Scenario {
+        pacifica_order: OrderBehavior::PartialFill(0.5),
+        ..Scenario::default()
+    })
+    .await;
+
+    run(&exchange).await.unwrap();
+
+    let state = exchange.state();
+    // Extended is the short leg, so it is trimmed to match the half filled long leg
+    assert_eq!(state.extended_orders.len(), 2);
+    assert_eq!(state.extended_orders[1]["side"], "BUY");
+    assert_eq!(state.pacifica_orders.len(), 1);
+
+    let extended = state.extended_positions[EXTENDED_MARKET].abs();
+    let pacifica = state.pacifica_positions[PACIFICA_MARKET].abs();
+    assert!((extended - pacifica).abs() < 0.001);
+}
+
+#[tokio::test]
+async fn short_leg_is_topped_up_when_it_underfills() {
+    let exchange = MockExchange::start(Scenario {
+        extended_order: OrderBehavior::PartialFill(0.8),
+        ..Scenario::default()
+    })
+    .await;
+
+    run(&exchange).await.unwrap();
+
+    let state = exchange.state();
+    assert!(state.extended_orders.len() > 1);
+    assert_eq!(state.extended_orders[1]["side"], "SELL");
+    // Only the entry carries a take profit and stop loss
+    assert!(!state.extended_orders[0]["takeProfit"].is_null());
+    assert!(state.extended_orders[1]["takeProfit"].is_null());
+
+    let extended = state.extended_positions[EXTENDED_MARKET].abs();
+    let pacifica = state.pacifica_positions[PACIFICA_MARKET].abs();
+    assert!((extended - pacifica).abs() < 0.001);
+}
+
+#[tokio::test]
+async fn maker_entry_hedges_fills_and_cancels_the_rest() {
+    let exchange = MockExchange::start(Scenario {
+        extended_order: OrderBehavior::PartialFill(0.5),
+        ..Scenario::default()
+    })
+    .await;
+
+    run_pair(
+        &exchange,
+        MarketPair {
+            entry_mode: EntryMode::Maker,
+            maker_timeout_secs: 1,
+            ..eth_pair()
+        },
+    )
+    .await
+    .unwrap();
+
+    let state = exchange.state();
+    assert_eq!(state.extended_orders[0]["type"], "LIMIT");
+    assert_eq!(state.extended_orders[0]["postOnly"], true);
+    // The resting leg is protected like the hedge
+    assert_eq!(state.extended_orders[0]["tpSlType"], "ORDER");
+    assert!(!state.extended_orders[0]["takeProfit"].is_null());
+    assert!(!state.extended_orders[0]["stopLoss"].is_null());
+    assert_eq!(state.extended_cancels.len(), 1);
+    // One hedge without TP/SL, then a single take profit and stop loss for it
+    assert_eq!(state.pacifica_orders.len(), 3);
+    assert_eq!(state.pacifica_orders[0]["side"], "bid");
+    assert!(state.pacifica_orders[0]["take_profit"].is_null());
+    let hedged = &state.pacifica_orders[0]["amount"];
+    for stop in &state.pacifica_orders[1..] {
+        assert_eq!(stop["side"], "ask");
+        assert_eq!(stop["reduce_only"], true);
+        assert_eq!(&stop["stop_order"]["amount"], hedged);
+    }
+
+    let extended = state.extended_positions[EXTENDED_MARKET].abs();
+    let pacifica = state.pacifica_positions[PACIFICA_MARKET].abs();
+    assert!((extended - pacifica).abs() < 0.001);
+}
+
+#[tokio::test]
+async fn twap_entry_sends_hedged_slices() {
+    let exchange = MockExchange::start(Scenario::default()).await;
+
+    run_pair(
+        &exchange,
+        MarketPair {
+            entry_mode: EntryMode::Twap,
+            twap_slices: 3,
+            twap_window_secs: 1,
+            buy_amount: 75.0,
+            ..eth_pair()
+        },
+    )
+    .await
+    .unwrap();
+
+    let state = exchange.state();
+    // Three children without TP/SL, then one set per leg for their total
+    assert_eq!(state.extended_orders.len(), 4);
+    assert_eq!(state.pacifica_orders.len(), 5);
+    for child in 0..3 {
+        assert!(state.extended_orders[child]["takeProfit"].is_null());
+        assert!(state.pacifica_orders[child]["take_profit"].is_null());
+    }
+    let child_qty = state.extended_orders[0]["qty"].as_str().unwrap();
+    let total = child_qty.parse::<f64>().unwrap() * 3.0;
+    let tp_sl = &state.extended_orders[3];
+    assert_eq!(tp_sl["type"], "TPSL");
+    assert!((tp_sl["qty"].as_str().unwrap().parse::<f64>().unwrap() - total).abs() < 1e-9);
+
+    let extended = state.extended_positions[EXTENDED_MARKET].abs();
+    let pacifica = state.pacifica_positions[PACIFICA_MARKET].abs();
+    assert!((extended - pacifica).abs() < 0.001);
+}
+
+#[tokio::test]
+async fn twap_entry_stops_when_spread_widens() {
+    let exchange = MockExchange::start(Scenario::default()).await;
+    let state = exchange.state.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        state.lock().unwrap().scenario.pacifica_price = 2100.0;
+    });
+
+    let error = run_pair(
+        &exchange,
+        MarketPair {
+            entry_mode: EntryMode::Twap,
+            twap_slices: 3,
+            twap_window_secs: 3,
+            buy_amount: 75.0,
+            ..eth_pair()
+        },
+    )
+    .await
+    .unwrap_err();
+
+    assert!(matches!(
+        bot_error(&error),
+        BotError::Skip(SkipReason::TwapAborted {
+            sent: 1,
+            slices: 3,
+            ..
+        })
+    ));
+    // The slice that was sent is still protected on both legs
+    let state = exchange.state();
+    assert_eq!(state.extended_orders.len(), 2);
+    assert_eq!(state.extended_orders[1]["type"], "TPSL");
+    assert_eq!(state.pacifica_orders.len(), 3);
+}
+
+#[tokio::test]
+async fn protective_orders_mirror_each_other_on_both_legs() {
+    let exchange = MockExchange::start(Scenario::default()).await;
+
+    run(&exchange).await.unwrap();
+
+    let state = exchange.state();
+    // Short on Extended, long on Pacifica, both 5% away with a 0.5% limit offset
+    let extended = &state.extended_orders[0];
+    assert_eq!(extended["tpSlType"], "ORDER");
+    assert_eq!(extended["takeProfit"]["triggerPrice"], "1900");
+    assert_eq!(extended["takeProfit"]["price"], "1910");
+    assert_eq!(extended["stopLoss"]["triggerPrice"], "2100");
+    assert_eq!(extended["stopLoss"]["price"], "2110");
+
+    let pacifica = &state.pacifica_orders[0];
+    assert_eq!(pacifica["take_profit"]["stop_price"], "2100");
+    assert_eq!(pacifica["take_profit"]["limit_price"], "2090");
+    assert_eq!(pacifica["stop_loss"]["stop_price"], "1900");
+    assert_eq!(pacifica["stop_loss"]["limit_price"], "1890");
+    assert_eq!(pacifica["reduce_only"], false);
+}
+
+#[tokio::test]
+async fn protective_orders_can_be_turned_off_per_pair() {
+    let exchange = MockExchange::start(Scenario::default()).await;
+    let mut pair = eth_pair();
+    pair.tp_sl.enabled = false;
+
+    run_pair(&exchange, pair).await.unwrap();
+
+    let state = exchange.state();
+    assert!(state.extended_orders[0]["takeProfit"].is_null());
+    assert!(state.pacifica_orders[0]["take_profit"].is_null());
+    // Still an opening order, not a reduce-only one
+    assert_eq!(state.pacifica_orders[0]["reduce_only"], false);
+}
+
+#[tokio::test]
+async fn atr_policy_scales_distances_with_volatility() {
+    let exchange = MockExchange::start(Scenario {
+        candle_range: 20.0,
+        ..Scenario::default()
+    })
+    .await;
+    let mut pair = eth_pair();
+    pair.tp_sl.mode = TpSlMode::Atr;
+    pair.tp_sl.take_profit = 3.0;
+    pair.tp_sl.stop_loss = 2.0;
+    pair.tp_sl.limit_offset = 0.0;
+
+    run_pair(&exchange, pair).await.unwrap();
+
+    let state = exchange.state();
+    // ATR of 20: take profit 60 and stop loss 40 away from 2000
+    assert_eq!(
+        state.extended_orders[0]["takeProfit"]["triggerPrice"],
+        "1940"
+    );
+    assert_eq!(state.extended_orders[0]["stopLoss"]["triggerPrice"], "2040");
+    assert_eq!(
+        state.pacifica_orders[0]["take_profit"]["stop_price"],
+        "2060"
+    );
+    assert_eq!(state.pacifica_orders[0]["stop_loss"]["stop_price"], "1960");
+    assert!(state.pacifica_orders[0]["take_profit"]["limit_price"].is_null());
+}
@@ -0,0 +1,166 @@
+mod common;
+
+use funding_rate_bot::{
+    config::structs::{AllocationConfig, MarketPair, PairLeg},
+    portfolio::structs::Portfolio,
+    sizing::structs::SizeLimit,
+    strategy::{
+        plan_entries::plan_entries,
+        structs::{EdgeEstimate, Opportunity},
+    },
+    venue::structs::{Balance, MarketSnapshot, Position, PositionSide, Side},
+};
+
+use common::mock_exchange::eth_pair;
+
+fn pair(market: &str, buy_amount: f64) -> MarketPair {
+    MarketPair {
+        extended: PairLeg {
+            market: format!("{}-USD", market),
+            slippage: 0.01,
+        },
+        pacifica: PairLeg {
+            market: market.to_string(),
+            slippage: 0.01,
+        },
+        buy_amount,
+        ..eth_pair()
+    }
+}
+
+fn opportunity(net_return: f64) -> Opportunity<(), ()> {
+    let snapshot = MarketSnapshot {
+        market: String::from("ETH"),
+        bid: 100.0,
+        ask: 100.0,
+        mark: 100.0,
+        funding_rate: 0.0001,
+        funding_interval_hours: 1.0,
+        max_funding_rate: None,
+        next_funding_time: None,
+        lot_size: 0.01,
+        max_order_value: None,
+        max_position_value: None,
+    };
+
+    Opportunity {
+        market_a: (),
+        market_b: (),
+        snapshot_a: snapshot.clone(),
+        snapshot_b: snapshot,
+        side_a: Side::Sell,
+        edge: EdgeEstimate {
+            funding: 0.0,
+            fees: 0.0,
+            slippage: 0.0,
+            basis: 0.0,
+            net: 0.0,
+        },
+        net_return,
+    }
+}
+
+fn portfolio(available_a: f64, positions: Vec<Position>) -> Portfolio {
+    Portfolio {
+        positions_a: positions,
+        positions_b: Vec::new(),
+        balance_a: Balance {
+            balance: 1000.0,
+            available_for_trade: available_a,
+        },
+        balance_b: Balance {
+            balance: 1000.0,
+            available_for_trade: 1000.0,
+        },
+    }
+}
+
+#[test]
+fn gives_capital_to_the_best_pairs_first() {
+    let (eth, sol, doge) = (pair("ETH", 100.0), pair("SOL", 100.0), pair("DOGE", 100.0));
+    let opportunities = vec![
+        (&eth, opportunity(0.1)),
+        (&sol, opportunity(0.5)),
+        (&doge, opportunity(0.3)),
+    ];
+
+    let planned = plan_entries(
+        opportunities,
+        &portfolio(1000.0, Vec::new()),
+        &AllocationConfig {
+            max_new_pairs: 2,
+            ..AllocationConfig::default()
+        },
+    );
+
+    let markets = planned
+        .iter()
+        .map(|entry| entry.pair.pacifica.market.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(markets, ["SOL", "DOGE"]);
+    assert!(planned.iter().all(|entry| entry.size.notional == 100.0));
+}
+
+#[test]
+fn shares_collateral_between_pairs() {
+    let (eth, sol) = (pair("ETH", 100.0), pair("SOL", 100.0));
+    let opportunities = vec![(&eth, opportunity(0.5)), (&sol, opportunity(0.3))];
+
+    // The second pair may use 90% of the 50 USD the first one left
+    let planned = plan_entries(
+        opportunities,
+        &portfolio(150.0, Vec::new()),
+        &AllocationConfig::default(),
+    );
+
+    assert_eq!(planned[0].size.notional, 100.0);
+    assert!((planned[1].size.notional - 45.0).abs() < 1e-9);
+    assert_eq!(planned[1].size.limited_by, SizeLimit::Collateral);
+}
+
+#[test]
+fn caps_market_and_total_exposure() {
+    let (eth, sol, doge) = (pair("ETH", 100.0), pair("SOL", 100.0), pair("DOGE", 100.0));
+    let opportunities = vec![
+        (&eth, opportunity(0.5)),
+        (&sol, opportunity(0.3)),
+        (&doge, opportunity(0.1)),
+    ];
+
+    // 150 USD already open leaves 100 USD of the 250 USD per venue
+    let held = Position::new(String::from("BTC-USD"), PositionSide::Short, 1.5, 100.0, 0);
+    let planned = plan_entries(
+        opportunities,
+        &portfolio(1000.0, vec![held]),
+        &AllocationConfig {
+            max_market_exposure: 60.0,
+            max_total_exposure: 250.0,
+            ..AllocationConfig::default()
+        },
+    );
+
+    assert_eq!(planned.len(), 2);
+    assert_eq!(planned[0].size.notional, 60.0);
+    assert!((planned[0].size.quantity - 0.6).abs() < 1e-9);
+    assert_eq!(planned[1].size.notional, 40.0);
+    assert_eq!(planned[1].size.limited_by, SizeLimit::Allocation);
+}
+
+#[test]
+fn stops_at_max_open_pairs() {
+    let (eth, sol) = (pair("ETH", 100.0), pair("SOL", 100.0));
+    let opportunities = vec![(&eth, opportunity(0.5)), (&sol, opportunity(0.3))];
+
+    let held = Position::new(String::from("BTC-USD"), PositionSide::Short, 1.0, 100.0, 0);
+    let planned = plan_entries(
+        opportunities,
+        &portfolio(1000.0, vec![held]),
+        &AllocationConfig {
+            max_open_pairs: 2,
+            ..AllocationConfig::default()
+        },
+    );
+
+    assert_eq!(planned.len(), 1);
+    assert_eq!(planned[0].pair.pacifica.market, "ETH");
+}
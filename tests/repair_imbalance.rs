@@ -0,0 +1,99 @@
+mod common;
+
+use funding_rate_bot::{
+    config::structs::{ImbalanceRepair, RebalanceConfig},
+    strategy::{repair_imbalance::repair_imbalance, structs::VenueLeg},
+    venue::perp_venue::PerpVenue,
+};
+
+use common::mock_exchange::{EXTENDED_MARKET, MockExchange, PACIFICA_MARKET, Scenario, eth_pair};
+
+async fn start(extended_size: f64, pacifica_size: f64) -> MockExchange {
+    let exchange = MockExchange::start(Scenario::default()).await;
+    {
+        let mut state = exchange.state();
+        state
+            .extended_positions
+            .insert(EXTENDED_MARKET.to_string(), extended_size);
+        state
+            .pacifica_positions
+            .insert(PACIFICA_MARKET.to_string(), pacifica_size);
+    }
+    exchange
+}
+
+async fn run(exchange: &MockExchange, repair: ImbalanceRepair) -> anyhow::Result<bool> {
+    let pair = eth_pair();
+    let extended = exchange.extended_venue();
+    let pacifica = exchange.pacifica_venue();
+    let extended_positions = extended.get_open_positions().await?;
+    let pacifica_positions = pacifica.get_open_positions().await?;
+
+    repair_imbalance(
+        VenueLeg {
+            venue: &extended,
+            leg: &pair.extended,
+        },
+        extended_positions.first(),
+        VenueLeg {
+            venue: &pacifica,
+            leg: &pair.pacifica,
+        },
+        pacifica_positions.first(),
+        1.0,
+        &RebalanceConfig {
+            enabled: true,
+            tolerance: 0.02,
+            repair,
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn leaves_balanced_hedge_alone() {
+    let exchange = start(-0.012, 0.0121).await;
+
+    assert!(!run(&exchange, ImbalanceRepair::Reduce).await.unwrap());
+
+    let state = exchange.state();
+    assert!(state.extended_orders.is_empty());
+    assert!(state.pacifica_orders.is_empty());
+}
+
+#[tokio::test]
+async fn reduces_the_larger_leg() {
+    let exchange = start(-0.02, 0.012).await;
+
+    assert!(run(&exchange, ImbalanceRepair::Reduce).await.unwrap());
+
+    let state = exchange.state();
+    assert_eq!(state.extended_orders.len(), 1);
+    assert_eq!(state.extended_orders[0]["side"], "BUY");
+    assert_eq!(state.extended_orders[0]["reduceOnly"], true);
+    assert!((state.extended_positions[EXTENDED_MARKET] + 0.012).abs() < 1e-9);
+    assert!(state.pacifica_orders.is_empty());
+}
+
+#[tokio::test]
+async fn hedges_the_smaller_leg() {
+    let exchange = start(-0.02, 0.012).await;
+
+    assert!(run(&exchange, ImbalanceRepair::Hedge).await.unwrap());
+
+    let state = exchange.state();
+    assert!(state.extended_orders.is_empty());
+    assert_eq!(state.pacifica_orders.len(), 1);
+    assert_eq!(state.pacifica_orders[0]["side"], "bid");
+    // The leg keeps the protective orders it was opened with
+    assert!(state.pacifica_orders[0]["take_profit"].is_null());
+    assert!((state.pacifica_positions[PACIFICA_MARKET] - 0.02).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn leaves_a_leg_without_hedge_to_the_orphan_policy() {
+    let exchange = start(-0.012, 0.0).await;
+
+    assert!(!run(&exchange, ImbalanceRepair::Reduce).await.unwrap());
+    assert!(exchange.state().extended_orders.is_empty());
+}
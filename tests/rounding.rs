@@ -0,0 +1,73 @@
+use std::str::FromStr;
+
+use funding_rate_bot::{
+    extended::orders::place_order::to_stark_amount,
+    utils::utils::{RoundingMode, round_to_min_change},
+};
+use rust_decimal::Decimal;
+
+fn dec(value: &str) -> Decimal {
+    Decimal::from_str(value).unwrap()
+}
+
+#[test]
+fn rounds_to_min_change() {
+    // 1000BONK-USD prices move in steps of 0.000001
+    let cases = [
+        ("0.0234567", "0.000001", RoundingMode::Floor, "0.023456"),
+        ("0.0234567", "0.000001", RoundingMode::Ceil, "0.023457"),
+        ("0.0234564", "0.000001", RoundingMode::Round, "0.023456"),
+        ("0.0234566", "0.000001", RoundingMode::Round, "0.023457"),
+        ("0.023456", "0.000001", RoundingMode::Ceil, "0.023456"),
+        // Trailing zeros of the step do not add decimal places
+        ("1.23456", "0.0010", RoundingMode::Floor, "1.234"),
+        ("1.23456", "0.0010", RoundingMode::Ceil, "1.235"),
+        ("1234.5", "10", RoundingMode::Round, "1230"),
+        ("0.3", "0.25", RoundingMode::Floor, "0.25"),
+    ];
+
+    for (value, min_change, mode, expected) in cases {
+        let rounded = round_to_min_change(dec(value), dec(min_change), Some(mode));
+        assert_eq!(
+            rounded,
+            dec(expected),
+            "{} by {} {:?}",
+            value,
+            min_change,
+            mode
+        );
+        assert_eq!(rounded.to_string(), expected);
+    }
+
+    assert_eq!(
+        round_to_min_change(dec("0.0234566"), dec("0.000001"), None),
+        dec("0.023457")
+    );
+}
+
+#[test]
+fn rounds_stark_amounts_in_the_venues_favour() {
+    // Buys pay a collateral amount rounded up, sells receive one rounded down
+    let cases = [
+        ("1234.5", true, 1235),
+        ("1234.5", false, 1234),
+        ("-1234.5", true, -1234),
+        ("-1234.5", false, -1235),
+        ("1000", true, 1000),
+        ("1000", false, 1000),
+        ("0.000001", true, 1),
+        ("0.999999", false, 0),
+    ];
+
+    for (amount, round_up, expected) in cases {
+        assert_eq!(
+            to_stark_amount(dec(amount), round_up).unwrap(),
+            expected,
+            "{} round up {}",
+            amount,
+            round_up
+        );
+    }
+
+    assert!(to_stark_amount(dec("1e20"), true).is_err());
+}